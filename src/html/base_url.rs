@@ -0,0 +1,162 @@
+use unhtml::scraper::{Html, Selector};
+use unhtml::url::Url;
+use unhtml::{FromHtml, FromHtmlRef, FromRow, Table};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Page {
+    #[html(selector = "a", attr = "href")]
+    links: Vec<Url>,
+
+    #[html(selector = "img", attr = "src")]
+    image: Option<Url>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Logo {
+    #[html(selector = "img", attr = "src")]
+    src: Url,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Link<'a> {
+    #[html(selector = "a", attr = "href")]
+    href: Url,
+
+    #[html(selector = "a", text)]
+    text: &'a str,
+}
+
+#[derive(FromRow, Debug, Eq, PartialEq)]
+struct Download {
+    #[html(column = "File", selector = "a", attr = "href")]
+    file: Url,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Downloads {
+    #[html(selector = "table")]
+    table: Table<Download>,
+}
+
+#[test]
+fn test_base_url() {
+    let html = r#"<a href="/about">About</a><a href="next?page=2">Next</a><a href="https://github.com">Github</a>"#;
+    let base = Url::parse("https://example.com/blog/index.html").unwrap();
+    let page = Page::from_html_with_base(html, &base).unwrap();
+    assert_eq!(
+        vec![
+            "https://example.com/about",
+            "https://example.com/blog/next?page=2",
+            "https://github.com/",
+        ],
+        page.links.iter().map(Url::as_str).collect::<Vec<_>>()
+    );
+    assert_eq!(None, page.image);
+}
+
+#[test]
+fn test_document_base() {
+    let html = r#"<head><base href="/static/"></head><body><img src="logo.png"><a href="/">Home</a></body>"#;
+    let base = Url::parse("https://example.com/blog/").unwrap();
+    let page = Page::from_html_with_base(html, &base).unwrap();
+    assert_eq!(
+        "https://example.com/static/logo.png",
+        page.image.unwrap().as_str()
+    );
+    assert_eq!("https://example.com/", page.links[0].as_str());
+
+    let html = r#"<base href="https://cdn.example.com/"><img src="logo.png">"#;
+    let page = Page::from_html_with_base(html, &base).unwrap();
+    assert_eq!(
+        "https://cdn.example.com/logo.png",
+        page.image.unwrap().as_str()
+    );
+    // an absolute `<base>` is enough without a base url
+    assert_eq!(
+        "https://cdn.example.com/logo.png",
+        Page::from_html(html).unwrap().image.unwrap().as_str()
+    );
+}
+
+#[test]
+fn test_document_with_base() {
+    let document = Html::parse_document(r#"<img src="logo.png">"#);
+    let base = Url::parse("https://example.com/blog/").unwrap();
+    let page = Page::from_document_with_base(&document, &base).unwrap();
+    assert_eq!(
+        "https://example.com/blog/logo.png",
+        page.image.unwrap().as_str()
+    );
+    // the document is left as it was
+    assert!(Logo::from_document(&document).is_err());
+    assert_eq!(1, document.tree.root().children().count());
+}
+
+#[test]
+fn test_element_with_base() {
+    let document = Html::parse_document(
+        r#"<ul><li><a href="apple">Apple</a></li><li><a href="/lemon">Lemon</a></li></ul>"#,
+    );
+    let base = Url::parse("https://example.com/fruits/").unwrap();
+    let selector = Selector::parse("li").unwrap();
+    let element = document.select(&selector).nth(1).unwrap();
+    let page = Page::from_element_with_base(element, &base).unwrap();
+    assert_eq!("https://example.com/lemon", page.links[0].as_str());
+    let link = Link::from_element_ref_with_base(element, &base).unwrap();
+    assert_eq!("https://example.com/lemon", link.href.as_str());
+
+    let links: Vec<_> = Link::iter_html_ref_with_base(&document, &selector, &base)
+        .map(|link| link.unwrap())
+        .collect();
+    assert_eq!("https://example.com/fruits/apple", links[0].href.as_str());
+    assert_eq!("Lemon", links[1].text);
+    let pages: Vec<_> = Page::iter_html_with_base(&document, &selector, &base)
+        .map(|page| page.unwrap().links)
+        .collect();
+    assert_eq!("https://example.com/lemon", pages[1][0].as_str());
+
+    let link = Link::from_document_ref_with_base(&document, &base).unwrap();
+    assert_eq!("https://example.com/fruits/apple", link.href.as_str());
+    assert!(Link::from_document_ref(&document).is_err());
+}
+
+#[test]
+fn test_row_with_base() {
+    let html = r#"<table><tr><th>File</th></tr><tr><td><a href="unhtml.tar.gz">unhtml</a></td></tr></table>"#;
+    let base = Url::parse("https://example.com/downloads/").unwrap();
+    let downloads = Downloads::from_html_with_base(html, &base).unwrap();
+    assert_eq!(
+        "https://example.com/downloads/unhtml.tar.gz",
+        downloads.table[0].file.as_str()
+    );
+}
+
+#[test]
+fn test_invalid_url() {
+    let err = Page::from_html(r#"<a href="/about">About</a>"#).unwrap_err();
+    assert_eq!("Page.links[0]", err.context().unwrap().field_path());
+    match err.root_cause() {
+        unhtml::Error::TextParseError { text, err, .. } => {
+            assert_eq!("/about", text);
+            assert_eq!("relative URL without a base", err);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+
+    let base = Url::parse("https://example.com").unwrap();
+    let html = r#"<a href="https://exa mple.com">About</a>"#;
+    assert!(Page::from_html_with_base(html, &base).is_err());
+}
+
+#[test]
+fn test_required_url() {
+    let err = Logo::from_html(r#"<img src="logo.png">"#).unwrap_err();
+    assert_eq!("Logo.src", err.context().unwrap().field_path());
+    match err.root_cause() {
+        unhtml::Error::TextParseError { text, err, .. } => {
+            assert_eq!("logo.png", text);
+            assert_eq!("relative URL without a base", err);
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
@@ -0,0 +1,94 @@
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "form")]
+struct Settings {
+    #[html(selector = ".lemon", text)]
+    like_lemon: bool,
+
+    #[html(selector = ".notify", text, truthy = "✓", falsy = "✗")]
+    notify: bool,
+
+    #[html(selector = ".dark", attr = "data-mode", truthy = "dark")]
+    dark: bool,
+
+    #[html(selector = "input[name=remember]", attr = "checked", presence)]
+    remember: bool,
+
+    #[html(selector = "input[name=submit]", attr = "disabled", presence)]
+    disabled: bool,
+}
+
+#[test]
+fn test_bool() {
+    let settings = Settings::from_html(
+        r#"
+        <form>
+            <span class="lemon"> Yes </span>
+            <span class="notify">✗</span>
+            <span class="dark" data-mode="light"></span>
+            <input name="remember" type="checkbox" checked>
+            <input name="submit" type="submit">
+        </form>
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        Settings {
+            like_lemon: true,
+            notify: false,
+            dark: false,
+            remember: true,
+            disabled: false,
+        },
+        settings
+    );
+}
+
+#[test]
+fn test_bool_unknown_word() {
+    let err = Settings::from_html(
+        r#"
+        <form>
+            <span class="lemon">yes</span>
+            <span class="notify">maybe</span>
+            <span class="dark" data-mode="dark"></span>
+        </form>
+        "#,
+    )
+    .unwrap_err();
+    assert_eq!("Settings.notify", err.context().unwrap().field_path());
+    assert_eq!(
+        "maybe cannot be parsed as bool: expected one of `✓`, `✗`",
+        err.root_cause().to_string()
+    );
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Flags {
+    #[html(selector = ".stock", text, truthy = "in stock", falsy = "sold out")]
+    stock: Option<bool>,
+
+    #[html(selector = ".sale", text, truthy = "sale", optional)]
+    sale: Option<bool>,
+}
+
+#[test]
+fn test_optional_bool() {
+    let flags = Flags::from_html(r#"<p class="stock">In stock</p><p class="sale">-</p>"#).unwrap();
+    assert_eq!(
+        Flags {
+            stock: Some(true),
+            sale: Some(false),
+        },
+        flags
+    );
+    let flags = Flags::from_html(r#"<p class="stock">unknown</p>"#).unwrap();
+    assert_eq!(
+        Flags {
+            stock: None,
+            sale: None,
+        },
+        flags
+    );
+}
@@ -0,0 +1,88 @@
+use super::foo::Link;
+use std::borrow::Cow;
+use unhtml::scraper::Html;
+use unhtml::{FromHtml, FromHtmlRef};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Item<'a> {
+    #[html(attr = "id")]
+    id: &'a str,
+
+    #[html(selector = "p", text)]
+    text: Cow<'a, str>,
+
+    #[html(selector = "span", text)]
+    tags: Vec<&'a str>,
+
+    #[html(selector = "a")]
+    link: Option<Link>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "ul")]
+struct Items<'a> {
+    #[html(selector = "li")]
+    items: Vec<Item<'a>>,
+
+    #[html(selector = "#missing", text)]
+    missing: Option<&'a str>,
+}
+
+#[test]
+fn test_borrow() {
+    let document = Html::parse_document(
+        r#"
+        <ul>
+            <li id="lemon"><p> Lemon </p><span>fruit</span><span>yellow</span></li>
+            <li id="apple">
+                <p>An <b>apple</b></p>
+                <a href="https://github.com">Github</a>
+            </li>
+        </ul>
+    "#,
+    );
+    let items = Items::from_document_ref(&document).unwrap();
+    assert_eq!(None, items.missing);
+    assert_eq!(
+        vec![
+            Item {
+                id: "lemon",
+                text: "Lemon".into(),
+                tags: vec!["fruit", "yellow"],
+                link: None,
+            },
+            Item {
+                id: "apple",
+                text: "Anapple".into(),
+                tags: vec![],
+                link: Some(Link {
+                    href: "https://github.com".into(),
+                    text: "Github".into(),
+                }),
+            },
+        ],
+        items.items
+    );
+    match &items.items[0].text {
+        Cow::Borrowed(_) => (),
+        Cow::Owned(text) => panic!("text `{}` should be borrowed", text),
+    }
+}
+
+#[test]
+fn test_borrow_split_text() {
+    #[derive(FromHtml, Debug)]
+    struct Paragraph<'a> {
+        #[html(selector = "p", text)]
+        text: &'a str,
+    }
+
+    let document = Html::parse_document("<p> apple </p>");
+    assert_eq!(
+        "apple",
+        Paragraph::from_document_ref(&document).unwrap().text
+    );
+    let document = Html::parse_document("<p>An <b>apple</b></p>");
+    let err = Paragraph::from_document_ref(&document).unwrap_err();
+    assert_eq!("Paragraph.text", err.context().unwrap().field_path());
+}
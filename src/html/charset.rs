@@ -0,0 +1,30 @@
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Page {
+    #[html(selector = "title", text)]
+    title: String,
+
+    #[html(selector = "li", text)]
+    items: Vec<String>,
+}
+
+#[test]
+fn test_from_bytes() {
+    let html = r#"<html><head><meta charset="Shift_JIS"><title>商品</title></head><body><ul><li>お茶</li><li>コーヒー</li></ul></body></html>"#;
+    let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(html);
+    let expected = Page {
+        title: "商品".into(),
+        items: vec!["お茶".into(), "コーヒー".into()],
+    };
+    assert_eq!(expected, Page::from_bytes(&bytes).unwrap());
+    assert_eq!(expected, Page::from_reader(&bytes[..]).unwrap());
+
+    let html = html.replace(r#"<meta charset="Shift_JIS">"#, "");
+    let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(&html);
+    assert_ne!(expected, Page::from_bytes(&bytes).unwrap());
+    assert_eq!(
+        expected,
+        Page::from_bytes_with_charset(&bytes, "text/html; charset=Shift_JIS").unwrap()
+    );
+}
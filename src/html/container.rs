@@ -0,0 +1,74 @@
+use std::collections::BTreeSet;
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Menu {
+    #[html(selector = "span", text)]
+    title: String,
+
+    #[html(selector = ".menu")]
+    submenu: Option<Box<Menu>>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "article")]
+struct Article {
+    #[html(selector = ".tag", text)]
+    tags: BTreeSet<String>,
+
+    #[html(selector = ".author", text)]
+    authors: [String; 2],
+}
+
+#[test]
+fn test_recursive() {
+    let menu = Menu::from_html(
+        r#"
+        <span>A</span>
+        <div class="menu">
+            <span>B</span>
+            <div class="menu"><span>C</span></div>
+        </div>
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        Menu {
+            title: "A".into(),
+            submenu: Some(Box::new(Menu {
+                title: "B".into(),
+                submenu: Some(Box::new(Menu {
+                    title: "C".into(),
+                    submenu: None,
+                })),
+            })),
+        },
+        menu
+    );
+}
+
+#[test]
+fn test_collections() {
+    let article = Article::from_html(
+        r#"
+        <article>
+            <i class="author">Hexilee</i>
+            <i class="author">BigBrother</i>
+            <b class="tag">rust</b>
+            <b class="tag">html</b>
+            <b class="tag">rust</b>
+        </article>
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["html", "rust"],
+        article.tags.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(["Hexilee", "BigBrother"], article.authors);
+
+    let err =
+        Article::from_html(r#"<article><i class="author">Hexilee</i></article>"#).unwrap_err();
+    assert_eq!("Article.authors", err.context().unwrap().field_path());
+    assert_eq!("expected 2 elements, found 1", err.root_cause().to_string());
+}
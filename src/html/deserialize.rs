@@ -0,0 +1,169 @@
+use serde::Deserialize;
+use std::borrow::Cow;
+use unhtml::de::{self, Deserializer, FieldMap};
+use unhtml::scraper::{Html, Selector};
+use unhtml::Error;
+
+#[derive(Deserialize, Debug, PartialEq)]
+enum Stock {
+    #[serde(rename = "in stock")]
+    InStock,
+    #[serde(rename = "sold out")]
+    SoldOut,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Product {
+    #[serde(rename = "h2")]
+    name: String,
+
+    #[serde(rename = ".price@data-value")]
+    price: f64,
+
+    #[serde(rename = ".stock")]
+    stock: Stock,
+
+    #[serde(default, rename = "li")]
+    tags: Vec<String>,
+
+    #[serde(rename = ".discount")]
+    discount: Option<u8>,
+
+    #[serde(rename = "@id")]
+    id: u32,
+
+    #[serde(rename = "input@checked")]
+    featured: Option<String>,
+
+    #[serde(default, rename = ".rating@data-value")]
+    rating: f64,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Page {
+    title: String,
+
+    #[serde(rename = ".product")]
+    products: Vec<Product>,
+}
+
+const HTML: &str = r#"
+<title>Shop</title>
+<div class="product" id="1">
+    <h2>Lemon tea</h2>
+    <span class="price" data-value="1.5">$1.50</span>
+    <span class="stock">in stock</span>
+    <ul><li>tea</li><li>lemon</li></ul>
+    <span class="discount">10</span>
+    <span class="rating" data-value="4.5"></span>
+    <input type="checkbox" checked>
+</div>
+<div class="product" id="2">
+    <h2>Coffee</h2>
+    <span class="price" data-value="2">$2.00</span>
+    <span class="stock">sold out</span>
+    <span class="rating"></span>
+    <input type="checkbox">
+</div>
+"#;
+
+#[test]
+fn test_deserialize() {
+    assert_eq!(
+        Page {
+            title: "Shop".into(),
+            products: vec![
+                Product {
+                    name: "Lemon tea".into(),
+                    price: 1.5,
+                    stock: Stock::InStock,
+                    tags: vec!["tea".into(), "lemon".into()],
+                    discount: Some(10),
+                    id: 1,
+                    featured: Some("".into()),
+                    rating: 4.5,
+                },
+                Product {
+                    name: "Coffee".into(),
+                    price: 2.0,
+                    stock: Stock::SoldOut,
+                    tags: vec![],
+                    discount: None,
+                    id: 2,
+                    featured: None,
+                    rating: 0.0,
+                },
+            ],
+        },
+        de::from_html(HTML).unwrap()
+    );
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Summary {
+    name: String,
+    price: f64,
+}
+
+#[test]
+fn test_deserialize_field_map() {
+    let fields = FieldMap::new().field("Summary", "name", "h2").field(
+        "Summary",
+        "price",
+        ".price@data-value",
+    );
+    let html = Html::parse_document(HTML);
+    let selector = Selector::parse(".product").unwrap();
+    let summaries =
+        Vec::<Summary>::deserialize(Deserializer::new(html.select(&selector)).with_fields(&fields))
+            .unwrap();
+    assert_eq!(
+        vec![
+            Summary {
+                name: "Lemon tea".into(),
+                price: 1.5
+            },
+            Summary {
+                name: "Coffee".into(),
+                price: 2.0
+            },
+        ],
+        summaries
+    );
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Borrowed<'a> {
+    title: &'a str,
+    #[serde(borrow, rename = ".price@data-value")]
+    price: Cow<'a, str>,
+}
+
+#[test]
+fn test_deserialize_borrowed() {
+    let html = Html::parse_document(HTML);
+    let borrowed: Borrowed = de::from_document(&html).unwrap();
+    assert_eq!("Shop", borrowed.title);
+    assert!(matches!(borrowed.price, Cow::Borrowed("1.5")));
+}
+
+#[test]
+fn test_deserialize_error() {
+    let html = HTML.replace(r#"data-value="2""#, r#"data-value="free""#);
+    let err = de::from_html::<Page>(&html).unwrap_err();
+    assert_eq!(
+        "Page..product[1]..price@data-value",
+        err.context().unwrap().field_path()
+    );
+    assert!(matches!(err.root_cause(), Error::TextParseError { .. }));
+
+    let err = de::from_html::<Summary>(HTML).unwrap_err();
+    assert!(err.root_cause().is_not_found());
+
+    let html = HTML.replace("<h2>Coffee</h2>", "");
+    let err = de::from_html::<Page>(&html).unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!("Page..product[1].h2", context.field_path());
+    assert_eq!(Some("h2"), context.selector.as_deref());
+    assert!(err.root_cause().is_not_found());
+}
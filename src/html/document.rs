@@ -0,0 +1,88 @@
+use unhtml::scraper::{Html, Selector};
+use unhtml::{FromHtml, FromHtmlRef};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Title {
+    #[html(selector = "title", text)]
+    title: String,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Body {
+    #[html(selector = "body > p", text)]
+    text: Option<String>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "li")]
+struct Items {
+    #[html(text)]
+    items: Vec<String>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Cell {
+    #[html(selector = "td", text)]
+    cells: Vec<String>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Link<'a> {
+    #[html(attr = "href")]
+    href: &'a str,
+
+    #[html(text)]
+    text: &'a str,
+}
+
+#[test]
+fn test_from_document() {
+    let document = Html::parse_document(
+        "<html><head><title>Menu</title></head><body><ul><li>Tea</li><li>Milk</li></ul></body></html>",
+    );
+    assert_eq!("Menu", Title::from_document(&document).unwrap().title);
+    assert_eq!(
+        vec!["Tea", "Milk"],
+        Items::from_document(&document).unwrap().items
+    );
+}
+
+#[test]
+fn test_from_fragment() {
+    let html = "<title>Menu</title><p>Tea</p>";
+    assert_eq!(Some("Tea".into()), Body::from_html(html).unwrap().text);
+    assert_eq!(None, Body::from_fragment(html).unwrap().text);
+    assert_eq!("Menu", Title::from_fragment(html).unwrap().title);
+}
+
+#[test]
+fn test_from_html_in_context() {
+    let html = "<tr><td>Tea</td><td>3</td></tr>";
+    assert!(Cell::from_fragment(html).unwrap().cells.is_empty());
+    assert_eq!(
+        vec!["Tea", "3"],
+        Cell::from_html_in_context(html, "tbody").unwrap().cells
+    );
+    assert_eq!(
+        vec!["Tea", "3"],
+        Cell::from_html_in_context(html, "TABLE").unwrap().cells
+    );
+}
+
+#[test]
+fn test_from_element() {
+    let document = Html::parse_fragment(r#"<p><a href="/tea">Tea</a><a href="/milk">Milk</a></p>"#);
+    let selector = Selector::parse("a").unwrap();
+    let links: Vec<_> = document
+        .select(&selector)
+        .map(|element| Link::from_element_ref(element).unwrap())
+        .collect();
+    assert_eq!("/milk", links[1].href);
+    assert_eq!("Milk", links[1].text);
+
+    let element = document.select(&selector).next().unwrap();
+    assert!(Title::from_element(element)
+        .unwrap_err()
+        .root_cause()
+        .is_not_found());
+}
@@ -0,0 +1,66 @@
+use super::foo::Link;
+use unhtml::{Error, FromHtml};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+enum Item {
+    #[html(selector = "a")]
+    Link(Link),
+
+    #[html(selector = "img")]
+    Image {
+        #[html(attr = "src")]
+        src: String,
+    },
+
+    #[html(selector = ".sold-out")]
+    SoldOut,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Items {
+    #[html(selector = ".item")]
+    items: Vec<Item>,
+}
+
+#[test]
+fn test_enum() {
+    assert_eq!(
+        Items::from_html(
+            r#"
+            <div class="item"><img src="lemon.png"></div>
+            <div class="item"><a href="https://github.com">Github</a></div>
+            <div class="item"><span class="sold-out"></span></div>
+        "#
+        )
+        .unwrap(),
+        Items {
+            items: vec![
+                Item::Image {
+                    src: "lemon.png".into()
+                },
+                Item::Link(Link {
+                    href: "https://github.com".into(),
+                    text: "Github".into(),
+                }),
+                Item::SoldOut,
+            ]
+        }
+    );
+}
+
+#[test]
+fn test_no_variant_matched() {
+    match Item::from_html(r#"<div class="item"><img></div>"#).unwrap_err() {
+        Error::NoVariantMatched { enum_name, errors } => {
+            assert_eq!("Item", enum_name);
+            let variants: Vec<_> = errors.iter().map(|(variant, _)| variant.as_str()).collect();
+            assert_eq!(vec!["Link", "Image", "SoldOut"], variants);
+            assert!(errors[0].1.is_not_found());
+            assert_eq!(
+                "Item::Image.src",
+                errors[1].1.context().unwrap().field_path()
+            );
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
@@ -0,0 +1,102 @@
+use super::foo::Link;
+use unhtml::{Error, FromHtml, PathSegment};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Websites {
+    #[html(selector = "a")]
+    links: Vec<Link>,
+}
+
+#[test]
+fn test_field_path() {
+    let err = Websites::from_html(
+        r##"
+        <div>
+            <a href="https://github.com"> Github </a>
+            <a> ZJU QSC </a>
+        </div>
+    "##,
+    )
+    .unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!("Websites.links[1].href", context.field_path());
+    assert_eq!(
+        vec![
+            PathSegment::Field("links".into()),
+            PathSegment::Index(1),
+            PathSegment::Field("href".into()),
+        ],
+        context.path
+    );
+    assert_eq!(Some("a"), context.selector.as_deref());
+    assert_eq!("<a> ZJU QSC </a>", context.snippet);
+    match err.root_cause() {
+        Error::AttrNotFound { attr, .. } => assert_eq!("href", attr),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn test_snippet_limit() {
+    let html = format!("<p>{}</p>", "a".repeat(100_000));
+    let err = Link::from_html(&html).unwrap_err();
+    let snippet = &err.context().unwrap().snippet;
+    assert!(snippet.starts_with("<html><head></head><body><p>aaa"));
+    assert!(snippet.ends_with("..."));
+    assert_eq!(128 + 3, snippet.chars().count());
+}
+
+#[test]
+fn test_source_not_found() {
+    let err = Link::from_html("<p></p>").unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!("Link.href", context.field_path());
+    assert_eq!(None, context.selector);
+    assert!(err.to_string().starts_with("`Link.href` in `<html>"));
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct User {
+    #[html(selector = "p:nth-child(1)", text)]
+    name: String,
+
+    #[html(selector = "p:nth-child(2)", text)]
+    age: u8,
+
+    #[html(selector = "a")]
+    links: Vec<Link>,
+}
+
+#[test]
+fn test_report() {
+    let html = r##"
+        <div>
+            <p>Hexilee</p>
+            <p>twenty</p>
+            <a> Github </a>
+            <a href="https://google.com"> Google </a>
+            <a> ZJU QSC </a>
+        </div>
+    "##;
+    match User::from_html(html).unwrap_err() {
+        Error::FieldError { context, .. } => assert_eq!("User.age", context.field_path()),
+        err => panic!("unexpected error: {}", err),
+    }
+    match User::from_html_report(html).unwrap_err() {
+        Error::Multiple(errors) => {
+            let paths: Vec<_> = errors
+                .iter()
+                .map(|err| err.context().unwrap().field_path())
+                .collect();
+            assert_eq!(
+                vec!["User.age", "User.links[0].href", "User.links[2].href"],
+                paths
+            );
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+    assert_eq!(
+        User::from_html(r#"<p>Hexilee</p><p>20</p>"#).unwrap(),
+        User::from_html_report(r#"<p>Hexilee</p><p>20</p>"#).unwrap()
+    );
+}
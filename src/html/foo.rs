@@ -8,7 +8,7 @@ pub struct Link {
     #[html(attr = "href")]
     pub href: String,
 
-    #[html(attr = "inner")]
+    #[html(text)]
     pub text: String,
 }
 
@@ -91,7 +91,7 @@ fn test_fail_element() {
     "##,
     );
     let result: Result<Link> = html.select(&foo_selector).element();
-    assert!(!result.is_ok());
+    assert!(result.is_err());
 }
 
 #[test]
@@ -0,0 +1,70 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use unhtml::scraper::{Html, Selector};
+use unhtml::{FromHtml, FromHtmlRef};
+
+static EXTRACTED: AtomicUsize = AtomicUsize::new(0);
+
+fn count_id(text: &str) -> Result<u32, std::num::ParseIntError> {
+    EXTRACTED.fetch_add(1, Ordering::SeqCst);
+    text.parse()
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Item {
+    #[html(attr = "data-id", parse_with = "count_id")]
+    id: u32,
+
+    #[html(selector = ".name", text)]
+    name: String,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Name<'a> {
+    #[html(selector = ".name", text)]
+    name: &'a str,
+}
+
+const HTML: &str = r#"
+<ul>
+    <li data-id="1"><span class="name">Tea</span></li>
+    <li data-id="2"><span class="name">Coffee</span></li>
+    <li data-id="x"><span class="name">Ad</span></li>
+    <li data-id="4"><span class="name">Milk</span></li>
+</ul>
+"#;
+
+#[test]
+fn test_iter_html() {
+    let document = Html::parse_document(HTML);
+    let selector = Selector::parse("li").unwrap();
+    let seen = [2];
+    let new_items: Vec<_> = Item::iter_html(&document, &selector)
+        .map(Result::unwrap)
+        .take_while(|item| !seen.contains(&item.id))
+        .collect();
+    assert_eq!(
+        vec![Item {
+            id: 1,
+            name: "Tea".into()
+        }],
+        new_items
+    );
+    assert_eq!(2, EXTRACTED.load(Ordering::SeqCst));
+
+    let err = Item::iter_html(&document, &selector)
+        .find_map(Result::err)
+        .unwrap();
+    assert_eq!("Item[2].id", err.context().unwrap().field_path());
+    assert_eq!(4, Item::iter_html(&document, &selector).count());
+}
+
+#[test]
+fn test_iter_html_ref() {
+    let document = Html::parse_document(HTML);
+    let selector = Selector::parse("li").unwrap();
+    let names: Vec<_> = Name::iter_html_ref(&document, &selector)
+        .skip(1)
+        .map(|name| name.unwrap().name)
+        .collect();
+    assert_eq!(vec!["Coffee", "Ad", "Milk"], names);
+}
@@ -0,0 +1,66 @@
+use unhtml::{FromHtml, Lenient};
+
+#[derive(FromHtml, Debug, PartialEq)]
+struct Product {
+    #[html(selector = ".name", text)]
+    name: String,
+
+    #[html(selector = ".price", text)]
+    price: f64,
+}
+
+#[derive(FromHtml, Debug)]
+struct Page {
+    #[html(selector = ".product")]
+    products: Lenient<Product>,
+
+    #[html(selector = ".price", text)]
+    prices: Lenient<f64>,
+}
+
+const HTML: &str = r#"
+<div class="product"><span class="name">Tea</span><span class="price">1.5</span></div>
+<div class="product"><span class="name">Ad</span></div>
+<div class="product"><span class="name">Coffee</span><span class="price">free</span></div>
+<div class="product"><span class="name">Milk</span><span class="price">0.9</span></div>
+"#;
+
+#[test]
+fn test_lenient() {
+    let page = Page::from_html(HTML).unwrap();
+    assert_eq!(
+        &[
+            Product {
+                name: "Tea".into(),
+                price: 1.5
+            },
+            Product {
+                name: "Milk".into(),
+                price: 0.9
+            },
+        ],
+        page.products.items()
+    );
+    assert_eq!(
+        vec![1, 2],
+        page.products.skipped_indices().collect::<Vec<_>>()
+    );
+    let (_, err) = &page.products.skipped()[1];
+    assert!(err.to_string().contains("[2].price"), "{}", err);
+    assert!(matches!(
+        err.root_cause(),
+        unhtml::Error::TextParseError { .. }
+    ));
+
+    assert_eq!(&[1.5, 0.9], page.prices.items());
+    assert_eq!(vec![1], page.prices.skipped_indices().collect::<Vec<_>>());
+    assert!(!page.prices.is_complete());
+}
+
+#[test]
+fn test_lenient_complete() {
+    let page = Page::from_html(&HTML.replace("free", "2")).unwrap();
+    assert_eq!(3, page.prices.len());
+    assert_eq!(vec![1], page.products.skipped_indices().collect::<Vec<_>>());
+    assert!(Page::from_html("").unwrap().products.is_complete());
+}
@@ -0,0 +1,145 @@
+use std::collections::{BTreeMap, HashMap};
+use unhtml::scraper::Html;
+use unhtml::{FromHtml, FromHtmlRef};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Link {
+    #[html(attr = "href")]
+    href: String,
+}
+
+#[derive(FromHtml, Debug)]
+#[html(selector = "#product")]
+struct Product {
+    #[html(
+        selector = "dl",
+        key(selector = "dt", text),
+        value(selector = "dd", text)
+    )]
+    specs: BTreeMap<String, String>,
+
+    #[html(
+        selector = "tr",
+        key(selector = "th", text),
+        value(selector = "td a"),
+        duplicate = "first"
+    )]
+    links: HashMap<String, Link>,
+
+    #[html(
+        selector = "li",
+        key(attr = "data-size"),
+        value(text),
+        duplicate = "last"
+    )]
+    stock: BTreeMap<u32, u32>,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "#product")]
+struct ProductRef<'a> {
+    #[html(
+        selector = "dl",
+        key(selector = "dt", text),
+        value(selector = "dd", text)
+    )]
+    specs: HashMap<&'a str, &'a str>,
+}
+
+const HTML: &str = r#"
+<div id="product">
+    <dl>
+        <dt>Color</dt><dd>Yellow</dd>
+        <dt>Weight</dt><dd>100g</dd>
+    </dl>
+    <table>
+        <tr><th>Wiki</th><td><a href="https://en.wikipedia.org/wiki/Lemon">Lemon</a></td></tr>
+        <tr><th>Shop</th><td><a href="https://shop.example.com">Shop</a></td></tr>
+        <tr><th>Wiki</th><td><a href="https://zh.wikipedia.org/wiki/Lemon">Lemon</a></td></tr>
+    </table>
+    <ul>
+        <li data-size="1">10</li>
+        <li data-size="2">20</li>
+        <li data-size="1">5</li>
+    </ul>
+</div>
+"#;
+
+#[test]
+fn test_map() {
+    let product = Product::from_html(HTML).unwrap();
+    assert_eq!(
+        vec![("Color", "Yellow"), ("Weight", "100g")],
+        product
+            .specs
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(2, product.links.len());
+    assert_eq!(
+        "https://en.wikipedia.org/wiki/Lemon",
+        product.links["Wiki"].href
+    );
+    assert_eq!(
+        vec![(1, 5), (2, 20)],
+        product.stock.into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_map_ref() {
+    let document = Html::parse_document(HTML);
+    let product = ProductRef::from_document_ref(&document).unwrap();
+    assert_eq!("100g", product.specs["Weight"]);
+}
+
+#[test]
+fn test_duplicate_key() {
+    #[derive(FromHtml, Debug)]
+    struct Specs {
+        #[html(
+            selector = "dl",
+            key(selector = "dt", text),
+            value(selector = "dd", text)
+        )]
+        specs: HashMap<String, String>,
+    }
+
+    let specs = Specs::from_html("<dl><dt>Color</dt><dd>Yellow</dd></dl>").unwrap();
+    assert_eq!("Yellow", specs.specs["Color"]);
+
+    let err =
+        Specs::from_html("<dl><dt>Color</dt><dd>Yellow</dd><dt>Color</dt><dd>Green</dd></dl>")
+            .unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!("Specs.specs[0]", context.field_path());
+    assert_eq!("<dt>Color</dt>", context.snippet);
+    assert_eq!("duplicate key", err.root_cause().to_string());
+
+    let err =
+        Specs::from_html("<dl><dt>Color</dt><dd>Yellow</dd><dt>Weight</dt></dl>").unwrap_err();
+    assert_eq!("expected 2 elements, found 1", err.root_cause().to_string());
+
+    #[derive(FromHtml, Debug)]
+    struct Rows {
+        #[html(
+            selector = "tr",
+            key(selector = "th", text),
+            value(selector = "td", text)
+        )]
+        rows: HashMap<String, String>,
+    }
+
+    let rows = Rows::from_html("<table><tr><th>A</th><th>B</th><td>1</td><td>2</td></tr></table>")
+        .unwrap();
+    assert_eq!("2", rows.rows["B"]);
+
+    let err = Rows::from_html(
+        "<table><tr><th>A</th><th>B</th><td>1</td><td>2</td></tr><tr><th>C</th></tr></table>",
+    )
+    .unwrap_err();
+    let context = err.context().unwrap();
+    assert_eq!("Rows.rows[1]", context.field_path());
+    assert_eq!("<tr><th>C</th></tr>", context.snippet);
+}
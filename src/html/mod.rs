@@ -1,3 +1,25 @@
+mod base_url;
+mod boolean;
+mod borrow;
+mod charset;
 mod compound;
+mod container;
+mod deserialize;
+mod document;
+mod enumeration;
+mod error;
 mod foo;
+mod items;
+mod lenient;
+mod map;
+mod optional;
+mod position;
+mod raw;
+mod regex;
 mod root_selector;
+mod source;
+mod stream;
+mod table;
+mod text_mode;
+mod with;
+mod xpath;
@@ -0,0 +1,56 @@
+use super::foo::Link;
+use unhtml::{Error, FromHtml};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Product {
+    #[html(selector = ".price", text, optional)]
+    price: Option<u32>,
+
+    #[html(selector = ".price", attr = "data-currency", optional)]
+    currency: Option<String>,
+
+    #[html(selector = "a", optional)]
+    link: Option<Link>,
+}
+
+#[test]
+fn test_optional_not_found() {
+    assert_eq!(
+        Product::from_html("<p></p>").unwrap(),
+        Product {
+            price: None,
+            currency: None,
+            link: None,
+        }
+    );
+    assert_eq!(
+        Product::from_html(
+            r#"<span class="price"> 12 </span><a href="https://github.com">Github</a>"#
+        )
+        .unwrap(),
+        Product {
+            price: Some(12),
+            currency: None,
+            link: Some(Link {
+                href: "https://github.com".into(),
+                text: "Github".into(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn test_optional_parse_error() {
+    let err = Product::from_html(r#"<span class="price">12.50 EUR</span>"#).unwrap_err();
+    assert_eq!("Product.price", err.context().unwrap().field_path());
+    match err.root_cause() {
+        Error::TextParseError { text, .. } => assert_eq!("12.50 EUR", text),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn test_optional_nested_error() {
+    let err = Product::from_html("<a>Github</a>").unwrap_err();
+    assert_eq!("Product.link.href", err.context().unwrap().field_path());
+}
@@ -0,0 +1,77 @@
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "ul", last)]
+struct Page {
+    #[html(selector = "li", text)]
+    first: String,
+
+    #[html(selector = "li", text, nth = 1)]
+    second: String,
+
+    #[html(selector = "li", text, last)]
+    last: String,
+
+    #[html(selector = "li", text, nth = 9, optional)]
+    tenth: Option<String>,
+
+    #[html(selector = "li", text, skip = 1)]
+    without_header: Vec<String>,
+
+    #[html(selector = "li", text, take = 2)]
+    first_two: Vec<String>,
+
+    #[html(selector = "li", text, skip = 1, take = 1)]
+    only_second: Vec<String>,
+
+    #[html(selector = "li", text, range = "1..-1")]
+    items: Vec<String>,
+
+    #[html(selector = "li", text, range = "-2..")]
+    last_two: Vec<String>,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Cells {
+    // cells of different rows, which `:nth-child` cannot pick
+    #[html(selector = "td", text, nth = 2)]
+    third: String,
+}
+
+const HTML: &str = r#"
+<ul><li>other page</li></ul>
+<ul>
+    <li>Header</li>
+    <li>Tea</li>
+    <li>Coffee</li>
+    <li>Ad</li>
+</ul>
+"#;
+
+#[test]
+fn test_position() {
+    assert_eq!(
+        Page {
+            first: "Header".into(),
+            second: "Tea".into(),
+            last: "Ad".into(),
+            tenth: None,
+            without_header: vec!["Tea".into(), "Coffee".into(), "Ad".into()],
+            first_two: vec!["Header".into(), "Tea".into()],
+            only_second: vec!["Tea".into()],
+            items: vec!["Tea".into(), "Coffee".into()],
+            last_two: vec!["Coffee".into(), "Ad".into()],
+        },
+        Page::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_position_across_parents() {
+    let html = "<table><tr><td>a</td><td>b</td></tr><tr><td>c</td></tr></table>";
+    assert_eq!(Cells { third: "c".into() }, Cells::from_html(html).unwrap());
+    assert!(Cells::from_html("<table><tr><td>a</td></tr></table>")
+        .unwrap_err()
+        .root_cause()
+        .is_not_found());
+}
@@ -0,0 +1,72 @@
+use unhtml::scraper::{ElementRef, Html};
+use unhtml::{Fragment, FromHtml, FromHtmlRef, InnerHtml, OuterHtml};
+
+#[derive(FromHtml)]
+#[html(selector = "a")]
+struct Link {
+    #[html(attr = "href")]
+    href: String,
+
+    source: String,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "article")]
+struct Article {
+    #[html(selector = "h1", text)]
+    title: String,
+
+    #[html(selector = "p")]
+    outer: OuterHtml,
+
+    #[html(selector = "p")]
+    inner: InnerHtml,
+
+    #[html(selector = "p")]
+    fragment: Fragment,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "article")]
+struct ArticleRef<'a> {
+    #[html(selector = "h1")]
+    title: ElementRef<'a>,
+}
+
+const HTML: &str = r#"
+<article>
+    <h1>Lemon</h1>
+    <p>Lemon is <a href="https://en.wikipedia.org/wiki/Lemon">yellow</a></p>
+</article>
+"#;
+
+#[test]
+fn test_default_source() {
+    let link = Link::from_html(r#"<a href="https://github.com">Github</a>"#).unwrap();
+    assert_eq!("https://github.com", link.href);
+    assert_eq!(r#"<a href="https://github.com">Github</a>"#, link.source);
+}
+
+#[test]
+fn test_raw_html() {
+    let article = Article::from_html(HTML).unwrap();
+    assert_eq!("Lemon", article.title);
+    assert_eq!(
+        r#"<p>Lemon is <a href="https://en.wikipedia.org/wiki/Lemon">yellow</a></p>"#,
+        &*article.outer
+    );
+    assert_eq!(
+        r#"Lemon is <a href="https://en.wikipedia.org/wiki/Lemon">yellow</a>"#,
+        &*article.inner
+    );
+    let link: Link = article.fragment.extract().unwrap();
+    assert_eq!("https://en.wikipedia.org/wiki/Lemon", link.href);
+}
+
+#[test]
+fn test_element_ref() {
+    let document = Html::parse_document(HTML);
+    let article = ArticleRef::from_document_ref(&document).unwrap();
+    assert_eq!("h1", article.title.value().name());
+    assert_eq!("Lemon", article.title.inner_html());
+}
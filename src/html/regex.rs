@@ -0,0 +1,56 @@
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, PartialEq)]
+#[html(selector = "div.product")]
+struct Product {
+    #[html(selector = ".price", text, regex = r"([\d.]+)")]
+    price: f64,
+
+    #[html(
+        selector = ".price",
+        text,
+        regex = r"(?P<amount>[\d.]+) (?P<currency>[A-Z]{3})",
+        regex_group = "currency"
+    )]
+    currency: String,
+
+    #[html(selector = "a", attr = "href", regex = r"/item/(\d+)")]
+    id: u32,
+
+    #[html(selector = ".stock", text, regex = r"\d+", optional)]
+    stock: Option<u32>,
+
+    #[html(selector = "li", text, regex = r"(\d+) g")]
+    weights: Vec<u32>,
+}
+
+const HTML: &str = r#"
+<div class="product">
+    <span class="price">Price: 12.50 EUR</span>
+    <a href="/item/42?ref=home">Lemon tea</a>
+    <span class="stock">Only 3 left</span>
+    <ul><li>Box: 250 g</li><li>Refill: 500 g</li></ul>
+</div>
+"#;
+
+#[test]
+fn test_regex() {
+    assert_eq!(
+        Product {
+            price: 12.5,
+            currency: "EUR".into(),
+            id: 42,
+            stock: Some(3),
+            weights: vec![250, 500],
+        },
+        Product::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_regex_no_match() {
+    let result = Product::from_html(&HTML.replace("12.50 EUR", "sold out"));
+    let err = result.unwrap_err();
+    assert!(err.to_string().contains("no match of regex"));
+    assert!(err.to_string().contains("sold out"));
+}
@@ -6,7 +6,7 @@ pub struct Link {
     #[html(attr = "href")]
     pub href: String,
 
-    #[html(attr = "inner")]
+    #[html(text)]
     pub text: String,
 }
 
@@ -0,0 +1,69 @@
+use std::borrow::Cow;
+use unhtml::scraper::Html;
+use unhtml::{FromHtml, FromHtmlRef};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "form")]
+struct Form {
+    #[html(selector = "label", text)]
+    text: String,
+
+    #[html(selector = "label", own_text)]
+    label: String,
+
+    #[html(selector = "label", own_text, text_mode = "join")]
+    label_words: String,
+
+    #[html(selector = "label", inner_html)]
+    inner_html: String,
+
+    #[html(selector = "label", outer_html)]
+    outer_html: String,
+
+    #[html(selector = "label > *", tag)]
+    children: Vec<String>,
+
+    #[html(selector = "x-field", attr = "inner")]
+    inner_attr: String,
+
+    #[html(selector = "x-field", tag, regex = r"x-(\w+)")]
+    component: String,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "form")]
+struct FormRef<'a> {
+    #[html(selector = "label", own_text)]
+    label: Cow<'a, str>,
+}
+
+const HTML: &str = r#"
+<form>
+    <label>Name <span class="badge">New</span> required</label>
+    <x-field inner="name"></x-field>
+</form>
+"#;
+
+#[test]
+fn test_sources() {
+    assert_eq!(
+        Form {
+            text: "NameNewrequired".into(),
+            label: "Namerequired".into(),
+            label_words: "Name required".into(),
+            inner_html: r#"Name <span class="badge">New</span> required"#.into(),
+            outer_html: r#"<label>Name <span class="badge">New</span> required</label>"#.into(),
+            children: vec!["span".into()],
+            inner_attr: "name".into(),
+            component: "field".into(),
+        },
+        Form::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_sources_ref() {
+    let document = Html::parse_document(HTML);
+    let form = FormRef::from_document_ref(&document).unwrap();
+    assert_eq!("Namerequired", form.label);
+}
@@ -0,0 +1,102 @@
+use std::io::{self, Read};
+use unhtml::{FromHtml, FromHtmlStream};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "#logs > li", stream)]
+struct Log {
+    #[html(selector = ".level", text)]
+    level: String,
+
+    #[html(selector = ".message", text)]
+    message: String,
+
+    #[html(attr = "data-line")]
+    line: u32,
+}
+
+fn log(level: &str, message: &str, line: u32) -> Log {
+    Log {
+        level: level.into(),
+        message: message.into(),
+        line,
+    }
+}
+
+const HTML: &str = r#"
+<!DOCTYPE html>
+<html>
+<head><title>Logs <li></title><script>if (a < b) { "<li>" }</script></head>
+<body>
+<ul id="other"><li data-line="0"><span class="level">skip</span></li></ul>
+<ul id="logs">
+    <li data-line="1"><span class="level">INFO</span><p class="message">started<br>server</li>
+    <li data-line="2"><span class="level">WARN</span><span class="message">slow &amp; steady</span>
+    <li data-line="3"><span class="level">ERROR</span><!-- no message --></li>
+    <li data-line="4"><span class="level">INFO</span><span class="message">stopped</span></li>
+</ul>
+</body>
+</html>
+"#;
+
+#[test]
+fn test_stream() {
+    let mut stream = Log::stream();
+    let mut logs = Vec::new();
+    for chunk in HTML.as_bytes().chunks(7) {
+        stream.feed(std::str::from_utf8(chunk).unwrap());
+        logs.extend(&mut stream);
+    }
+    stream.finish();
+    logs.extend(&mut stream);
+    assert_eq!(4, logs.len());
+    assert_eq!(&log("INFO", "startedserver", 1), logs[0].as_ref().unwrap());
+    assert_eq!(&log("WARN", "slow & steady", 2), logs[1].as_ref().unwrap());
+    assert!(logs[2].as_ref().unwrap_err().root_cause().is_not_found());
+    assert_eq!(&log("INFO", "stopped", 4), logs[3].as_ref().unwrap());
+}
+
+#[test]
+fn test_stream_items_when_closed() {
+    let mut stream = Log::stream();
+    stream.feed(r#"<ul id="logs"><li data-line="1"><span class="level">INFO</span>"#);
+    stream.feed(r#"<span class="message">started</span>"#);
+    assert!(stream.next().is_none());
+    stream.feed("</li><li>");
+    assert_eq!(log("INFO", "started", 1), stream.next().unwrap().unwrap());
+    assert!(stream.next().is_none());
+}
+
+/// a reader returning a few bytes at a time, splitting utf-8 chars
+struct Trickle<'a>(&'a [u8]);
+
+impl<'a> Read for Trickle<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.0.len().min(buf.len()).min(3);
+        buf[..len].copy_from_slice(&self.0[..len]);
+        self.0 = &self.0[len..];
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_stream_reader() {
+    let html = HTML.replace("stopped", "停止");
+    let logs: Vec<_> = Log::stream_reader(Trickle(html.as_bytes()))
+        .filter_map(Result::ok)
+        .collect();
+    assert_eq!(3, logs.len());
+    assert_eq!(log("INFO", "停止", 4), logs[2]);
+
+    let mut invalid = Log::stream_reader(
+        &b"<ul id=logs><li data-line=5><span class=level>INFO</span><span class=message>ok</span>\xff</li></ul>"[..],
+    );
+    assert!(matches!(invalid.next(), Some(Err(unhtml::Error::Io(_)))));
+    assert_eq!(log("INFO", "ok", 5), invalid.next().unwrap().unwrap());
+    assert!(invalid.next().is_none());
+}
+
+#[test]
+fn test_stream_same_as_document() {
+    let first = Log::stream_reader(HTML.as_bytes()).next().unwrap().unwrap();
+    assert_eq!(Log::from_html(HTML).unwrap(), first);
+}
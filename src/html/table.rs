@@ -0,0 +1,88 @@
+use unhtml::scraper::Html;
+use unhtml::{FromHtml, FromHtmlRef, FromRow, Table};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Link {
+    #[html(attr = "href")]
+    href: String,
+}
+
+#[derive(FromRow, Debug, Eq, PartialEq)]
+struct Fruit {
+    #[html(column = "Name", text)]
+    name: String,
+
+    #[html(column = "Price", text)]
+    price: u32,
+
+    #[html(column = "Wiki", selector = "a")]
+    wiki: Option<Link>,
+
+    #[html(attr = "data-id")]
+    id: u32,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+struct Shop {
+    #[html(selector = "#fruits")]
+    fruits: Table<Fruit>,
+}
+
+#[derive(FromRow)]
+struct FruitRef<'a> {
+    #[html(column = "Name", text)]
+    name: &'a str,
+}
+
+const HTML: &str = r#"
+<table id="fruits">
+    <thead>
+        <tr><th>Wiki</th><th>Name</th><th>Price</th></tr>
+    </thead>
+    <tbody>
+        <tr data-id="1">
+            <td><a href="https://en.wikipedia.org/wiki/Lemon">Lemon</a></td>
+            <td>Lemon</td>
+            <td>3</td>
+        </tr>
+        <tr data-id="2"><td></td><td>Apple</td><td>5</td></tr>
+    </tbody>
+</table>
+"#;
+
+#[test]
+fn test_table() {
+    let shop = Shop::from_html(HTML).unwrap();
+    assert_eq!(
+        vec![
+            Fruit {
+                name: "Lemon".into(),
+                price: 3,
+                wiki: Some(Link {
+                    href: "https://en.wikipedia.org/wiki/Lemon".into()
+                }),
+                id: 1,
+            },
+            Fruit {
+                name: "Apple".into(),
+                price: 5,
+                wiki: None,
+                id: 2,
+            },
+        ],
+        shop.fruits.into_rows()
+    );
+}
+
+#[test]
+fn test_table_ref() {
+    let document = Html::parse_document(HTML);
+    let table = Table::<FruitRef>::from_document_ref(&document).unwrap();
+    assert_eq!("Apple", table[1].name);
+}
+
+#[test]
+fn test_table_error() {
+    let err = Shop::from_html(&HTML.replace("<td>5</td>", "<td>five</td>")).unwrap_err();
+    assert_eq!("Shop.fruits[1].price", err.context().unwrap().field_path());
+}
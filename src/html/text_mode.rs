@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+use unhtml::scraper::Html;
+use unhtml::{FromHtml, FromHtmlRef};
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "article")]
+struct Article {
+    #[html(selector = "h1", text)]
+    glued: String,
+
+    #[html(selector = "h1", text, text_mode = "join")]
+    title: String,
+
+    #[html(selector = ".price", text, text_mode = "collapse")]
+    price: String,
+
+    #[html(selector = ".body", text, text_mode = "block")]
+    body: String,
+
+    #[html(selector = "li", text, text_mode = "collapse")]
+    tags: Vec<String>,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "article")]
+struct ArticleRef<'a> {
+    #[html(selector = "h1", text, text_mode = "join")]
+    title: Cow<'a, str>,
+}
+
+const HTML: &str = r#"
+<article>
+    <h1>Hello <b>World</b></h1>
+    <span class="price">12&nbsp;EUR</span>
+    <div class="body"><p>First line<br>second line</p><p>Next   paragraph</p></div>
+    <ul><li> lemon   tea </li><li>apple</li></ul>
+</article>
+"#;
+
+#[test]
+fn test_text_mode() {
+    assert_eq!(
+        Article {
+            glued: "HelloWorld".into(),
+            title: "Hello World".into(),
+            price: "12 EUR".into(),
+            body: "First line\nsecond line\nNext paragraph".into(),
+            tags: vec!["lemon tea".into(), "apple".into()],
+        },
+        Article::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_text_mode_ref() {
+    let document = Html::parse_document(HTML);
+    let article = ArticleRef::from_document_ref(&document).unwrap();
+    assert_eq!("Hello World", article.title);
+}
@@ -0,0 +1,106 @@
+use std::str::FromStr;
+use std::time::Duration;
+use unhtml::scraper::ElementRef;
+use unhtml::{ElemIter, FromHtml, FromHtmlRef};
+
+mod helpers {
+    use unhtml::{ElemIter, Result};
+
+    pub fn count(select: ElemIter) -> Result<usize> {
+        Ok(select.count())
+    }
+}
+
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let (minutes, seconds) = text
+        .split_once(':')
+        .ok_or_else(|| "expected `minutes:seconds`".to_owned())?;
+    let minutes: u64 = minutes.parse().map_err(|err| format!("{}", err))?;
+    let seconds: u64 = seconds.parse().map_err(|err| format!("{}", err))?;
+    Ok(Duration::from_secs(minutes * 60 + seconds))
+}
+
+fn classes<'a>(select: ElemIter<'_, 'a>) -> unhtml::Result<Vec<&'a str>> {
+    Ok(select.flat_map(|elem| elem.value().classes()).collect())
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+#[html(selector = "#album")]
+struct Album {
+    #[html(selector = "li", with = "helpers::count")]
+    tracks: usize,
+
+    #[html(selector = ".length", text, parse_with = "parse_duration")]
+    length: Duration,
+
+    #[html(
+        selector = ".intro",
+        attr = "data-length",
+        parse_with = "parse_duration",
+        optional
+    )]
+    intro: Option<Duration>,
+
+    #[html(
+        selector = ".released",
+        text,
+        regex = r"(\d{4})",
+        parse_with = "u16::from_str"
+    )]
+    year: u16,
+}
+
+#[derive(FromHtml)]
+#[html(selector = "#album")]
+struct AlbumRef<'a> {
+    #[html(selector = "li", with = "classes")]
+    classes: Vec<&'a str>,
+
+    #[html(with = "first_track")]
+    first: ElementRef<'a>,
+}
+
+fn first_track<'a>(select: ElemIter<'_, 'a>) -> unhtml::Result<ElementRef<'a>> {
+    let album = select.next().ok_or(())?;
+    Ok(album
+        .children()
+        .filter_map(ElementRef::wrap)
+        .next()
+        .ok_or(())?)
+}
+
+const HTML: &str = r#"
+<div id="album">
+    <ul><li class="hit">Intro</li><li class="live">Outro</li></ul>
+    <span class="length">41:07</span>
+    <span class="released">Released in 1997 (remastered)</span>
+</div>
+"#;
+
+#[test]
+fn test_with() {
+    assert_eq!(
+        Album {
+            tracks: 2,
+            length: Duration::from_secs(41 * 60 + 7),
+            intro: None,
+            year: 1997,
+        },
+        Album::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_with_error() {
+    let err = Album::from_html(&HTML.replace("41:07", "41 minutes")).unwrap_err();
+    assert!(err.to_string().contains("expected `minutes:seconds`"));
+    assert!(err.to_string().contains("41 minutes"));
+}
+
+#[test]
+fn test_with_ref() {
+    let document = unhtml::scraper::Html::parse_document(HTML);
+    let album = AlbumRef::from_document_ref(&document).unwrap();
+    assert_eq!(vec!["hit", "live"], album.classes);
+    assert_eq!("ul", album.first.value().name());
+}
@@ -0,0 +1,81 @@
+use unhtml::FromHtml;
+
+#[derive(FromHtml, Debug, PartialEq)]
+#[html(xpath = "//div[@class='product'][1]")]
+struct Product {
+    #[html(xpath = "dl/dt[text()='Price']/following-sibling::dd[1]", text)]
+    price: f64,
+
+    #[html(
+        xpath = "dl/dt[normalize-space()='Color']/following-sibling::dd[1]",
+        text
+    )]
+    color: String,
+
+    #[html(
+        xpath = "dl/dt[text()='Weight']/following-sibling::dd[1]",
+        text,
+        optional
+    )]
+    weight: Option<f64>,
+
+    #[html(xpath = ".//li[not(@hidden)]", text)]
+    tags: Vec<String>,
+
+    #[html(selector = "a", attr = "href")]
+    link: String,
+}
+
+#[derive(FromHtml, Debug, Eq, PartialEq)]
+enum Stock {
+    #[html(xpath = "//span[contains(@class, 'in-stock')]")]
+    InStock(#[html(text)] String),
+    #[html(xpath = "//span[@class='sold-out']")]
+    SoldOut,
+}
+
+const HTML: &str = r#"
+<div class="product">
+    <dl>
+        <dt>Color</dt><dd>Yellow</dd>
+        <dt>Price</dt><dd>12.5</dd>
+    </dl>
+    <ul><li>tea</li><li hidden>secret</li><li>lemon</li></ul>
+    <a href="/item/1">Lemon tea</a>
+    <span class="stock in-stock">3 left</span>
+</div>
+<div class="product"><dl><dt>Price</dt><dd>1</dd></dl></div>
+"#;
+
+#[test]
+fn test_xpath() {
+    assert_eq!(
+        Product {
+            price: 12.5,
+            color: "Yellow".into(),
+            weight: None,
+            tags: vec!["tea".into(), "lemon".into()],
+            link: "/item/1".into(),
+        },
+        Product::from_html(HTML).unwrap()
+    );
+}
+
+#[test]
+fn test_xpath_enum() {
+    assert_eq!(
+        Stock::InStock("3 left".into()),
+        Stock::from_html(HTML).unwrap()
+    );
+    let sold_out = HTML.replace(r#"class="stock in-stock""#, r#"class="sold-out""#);
+    assert_eq!(Stock::SoldOut, Stock::from_html(&sold_out).unwrap());
+}
+
+#[test]
+fn test_xpath_error() {
+    let html = HTML.replace("<dt>Price</dt><dd>12.5</dd>", "");
+    let err = Product::from_html(&html).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("dl/dt[text()='Price']/following-sibling::dd[1]"));
+}
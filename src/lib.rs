@@ -1,4 +1,6 @@
 #![cfg(test)]
+// tests spell failures as `!result.is_ok()`
+#![allow(clippy::nonminimal_bool)]
 
 mod html;
 mod text;
@@ -173,3 +173,68 @@ fn test_fail_attr() {
     let foo_result: Result<U8, Error> = html.select(&foo_selector).attr("value");
     assert!(!foo_result.is_ok());
 }
+
+#[derive(Debug, FromText, Eq, PartialEq)]
+#[html(case_insensitive)]
+enum Availability {
+    #[html(rename = "In stock", alias = "available")]
+    InStock,
+    #[html(rename = "Sold out")]
+    SoldOut,
+    Preorder,
+}
+
+#[derive(Debug, FromText, Eq, PartialEq)]
+enum Status {
+    Active,
+    Closed,
+}
+
+#[test]
+fn test_enum_inner_text() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(
+        r##"
+        <div>
+            <p> In Stock </p>
+            <p>AVAILABLE</p>
+            <p>sold out</p>
+            <p>preorder</p>
+        </div>
+    "##,
+    );
+    let result: Vec<Availability> = html.select(&selector).inner_text().unwrap();
+    assert_eq!(
+        result,
+        vec![
+            Availability::InStock,
+            Availability::InStock,
+            Availability::SoldOut,
+            Availability::Preorder
+        ]
+    );
+}
+
+#[test]
+fn test_enum_attr() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(
+        r##"
+        <div>
+            <p data-status="Active"></p>
+            <p data-status=" Closed "></p>
+            <p data-status="closed"></p>
+        </div>
+    "##,
+    );
+    let result: Vec<Status> = html.select(&selector).take(2).attr("data-status").unwrap();
+    assert_eq!(result, vec![Status::Active, Status::Closed]);
+    let result: Result<Vec<Status>, Error> = html.select(&selector).attr("data-status");
+    match result.unwrap_err().root_cause() {
+        Error::TextParseError { text, err, .. } => {
+            assert_eq!("closed", text);
+            assert_eq!("expected one of `Active`, `Closed`", err);
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+}
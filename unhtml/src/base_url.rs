@@ -0,0 +1,91 @@
+use crate::{
+    ElemIter, Extraction, FromSource, FromSourceRef, FromText, FromTextRef, Result, Source,
+};
+use scraper::ElementRef;
+use url::Url;
+
+/// resolve `text` against the base url of the extraction
+fn resolve(text: &str, element: ElementRef, extraction: &Extraction) -> Result<Url> {
+    let text = text.trim();
+    extraction
+        .base_url(element)
+        .map_or_else(|| Url::parse(text), |base| base.join(text))
+        .map_err(|err| (text.to_owned(), "Url".to_owned(), err.to_string()).into())
+}
+
+/// absolute urls, or relative ones resolved against the base url of the extraction
+impl FromText for Url {
+    fn from_inner_text(select: ElemIter) -> Result<Self> {
+        Self::from_inner_text_with(select, &Extraction::default())
+    }
+
+    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+        Self::from_attr_with(select, attr, &Extraction::default())
+    }
+
+    fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        let text: String = first.text().map(str::trim).collect();
+        resolve(&text, first, extraction)
+    }
+
+    fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        let value = first
+            .value()
+            .attr(attr)
+            .ok_or((attr.to_owned(), first.html()))?;
+        resolve(value, first, extraction)
+    }
+}
+
+impl FromSource for Url {
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        Self::from_source_with(select, source, &Extraction::default())
+    }
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        resolve(&source.text(first)?, first, extraction)
+    }
+}
+
+impl<'a> FromTextRef<'a> for Url {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_inner_text(select)
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        Self::from_attr(select, attr)
+    }
+
+    fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        Self::from_inner_text_with(select, extraction)
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_attr_with(select, attr, extraction)
+    }
+}
+
+impl<'a> FromSourceRef<'a> for Url {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        Self::from_source(select, source)
+    }
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_source_with(select, source, extraction)
+    }
+}
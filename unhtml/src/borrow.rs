@@ -0,0 +1,365 @@
+use crate::{ElemIter, Error, Extraction, Items, Result, Source};
+use scraper::{ElementRef, Html, Selector};
+use std::borrow::Cow;
+use std::iter;
+
+/// parse html into a type borrowing from the parsed document
+///
+/// It's implemented by `#[derive(FromHtml)]` for every struct or enum,
+/// a struct or enum with a lifetime parameter implements only `FromHtmlRef`.
+///
+/// ```rust,ignore
+/// use unhtml::{scraper::Html, FromHtml, FromHtmlRef};
+///
+/// #[derive(FromHtml)]
+/// #[html(selector = "a")]
+/// struct Link<'a> {
+///     #[html(attr = "href")]
+///     href: &'a str,
+/// }
+///
+/// let document = Html::parse_document(r#"<a href="https://github.com">Github</a>"#);
+/// let link = Link::from_document_ref(&document).unwrap();
+/// assert_eq!("https://github.com", link.href);
+/// ```
+pub trait FromHtmlRef<'a>: Sized {
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self>;
+
+    /// like `from_elements_ref`, but keep going after a failed field
+    /// and return all errors as an `Error::Multiple`
+    fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_elements_ref(select)
+    }
+
+    /// like `FromHtml::from_elements_with`
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, _extraction: &Extraction) -> Result<Self> {
+        Self::from_elements_ref(select)
+    }
+
+    fn from_elements_ref_report_with(
+        select: ElemIter<'_, 'a>,
+        _extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_elements_ref_report(select)
+    }
+
+    fn from_document_ref(document: &'a Html) -> Result<Self> {
+        Self::from_elements_ref(&mut iter::once(document.root_element()))
+    }
+
+    fn from_document_ref_report(document: &'a Html) -> Result<Self> {
+        Self::from_elements_ref_report(&mut iter::once(document.root_element()))
+    }
+
+    /// like `FromHtml::from_document_with_base`, the result borrows from `document`
+    #[cfg(feature = "url")]
+    fn from_document_ref_with_base(document: &'a Html, base: &url::Url) -> Result<Self> {
+        Self::from_element_ref_with_base(document.root_element(), base)
+    }
+
+    /// like `FromHtml::from_element`, the result borrows from `element`
+    fn from_element_ref(element: ElementRef<'a>) -> Result<Self> {
+        Self::from_elements_ref(&mut iter::once(element))
+    }
+
+    /// like `FromHtml::from_element_with_base`, the result borrows from `element`
+    #[cfg(feature = "url")]
+    fn from_element_ref_with_base(element: ElementRef<'a>, base: &url::Url) -> Result<Self> {
+        let extraction = Extraction::new().with_base(base.clone());
+        Self::from_elements_ref_with(&mut iter::once(element), &extraction)
+    }
+
+    /// like `FromHtml::iter_html`, items borrow from `document`
+    fn iter_html_ref(document: &'a Html, selector: &'a Selector) -> Items<'a, 'a, Self> {
+        Items::new(document.select(selector), Self::from_elements_ref_with)
+    }
+
+    /// like `FromHtml::iter_html_with_base`, items borrow from `document`
+    #[cfg(feature = "url")]
+    fn iter_html_ref_with_base(
+        document: &'a Html,
+        selector: &'a Selector,
+        base: &url::Url,
+    ) -> Items<'a, 'a, Self> {
+        Items::new(document.select(selector), Self::from_elements_ref_with)
+            .with_extraction(Extraction::new().with_base(base.clone()))
+    }
+}
+
+/// parse text or attribute into a type borrowing from the parsed document
+pub trait FromTextRef<'a>: Sized {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self>;
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self>;
+
+    /// like `FromText::from_inner_text_with`
+    fn from_inner_text_ref_with(
+        select: ElemIter<'_, 'a>,
+        _extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_inner_text_ref(select)
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        _extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_attr_ref(select, attr)
+    }
+}
+
+/// like `FromSource`, for types borrowing from the parsed document
+pub trait FromSourceRef<'a>: Sized {
+    /// parse the text taken from the first element by `source`
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self>;
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        _extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_source_ref(select, source)
+    }
+}
+
+/// text of the first element, borrowed if it's in only one text node
+pub(crate) fn borrow_text<'a>(select: ElemIter<'_, 'a>) -> Result<Cow<'a, str>> {
+    let first = select.next().ok_or(())?;
+    let mut segments = first
+        .text()
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty());
+    Ok(match (segments.next(), segments.next()) {
+        (None, _) => Cow::Borrowed(""),
+        (Some(segment), None) => Cow::Borrowed(segment),
+        (Some(_), Some(_)) => Cow::Owned(first.text().map(str::trim).collect()),
+    })
+}
+
+pub(crate) fn borrow_attr<'a>(select: ElemIter<'_, 'a>, attr: &str) -> Result<&'a str> {
+    let first = select.next().ok_or(())?;
+    let value = first
+        .value()
+        .attr(attr)
+        .ok_or((attr.to_owned(), first.html()))?;
+    Ok(value.trim())
+}
+
+impl<'a> FromTextRef<'a> for Cow<'a, str> {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        borrow_text(select)
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        borrow_attr(select, attr).map(Cow::Borrowed)
+    }
+}
+
+impl<'a> FromSourceRef<'a> for Cow<'a, str> {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Ok(Cow::Owned(source.text(first)?))
+    }
+}
+
+impl<'a> FromTextRef<'a> for &'a str {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        match borrow_text(select)? {
+            Cow::Borrowed(text) => Ok(text),
+            Cow::Owned(text) => Err((
+                text,
+                "&str".to_owned(),
+                "text in multiple nodes cannot be borrowed, use Cow<str> instead".to_owned(),
+            )
+                .into()),
+        }
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        borrow_attr(select, attr)
+    }
+}
+
+impl<'a> FromSourceRef<'a> for &'a str {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Err((
+            source.text(first)?,
+            "&str".to_owned(),
+            "extracted text cannot be borrowed, use Cow<str> instead".to_owned(),
+        )
+            .into())
+    }
+}
+
+impl<'a, T> FromTextRef<'a> for Option<T>
+where
+    T: FromTextRef<'a>,
+{
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_inner_text_ref_with(select, &Extraction::default())
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        Self::from_attr_ref_with(select, attr, &Extraction::default())
+    }
+
+    fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_inner_text_ref_with(select, extraction).ok())
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(T::from_attr_ref_with(select, attr, extraction).ok())
+    }
+}
+
+impl<'a, T> FromSourceRef<'a> for Option<T>
+where
+    T: FromSourceRef<'a>,
+{
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        Self::from_source_ref_with(select, source, &Extraction::default())
+    }
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(T::from_source_ref_with(select, source, extraction).ok())
+    }
+}
+
+impl<'a, T> FromHtmlRef<'a> for Option<T>
+where
+    T: FromHtmlRef<'a>,
+{
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_elements_ref_with(select, &Extraction::default())
+    }
+
+    fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_elements_ref_report_with(select, &Extraction::default())
+    }
+
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_elements_ref_with(select, extraction).ok())
+    }
+
+    fn from_elements_ref_report_with(
+        select: ElemIter<'_, 'a>,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(T::from_elements_ref_report_with(select, extraction).ok())
+    }
+}
+
+impl<'a, T> FromTextRef<'a> for Vec<T>
+where
+    T: FromTextRef<'a>,
+{
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_inner_text_ref_with(select, &Extraction::default())
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        Self::from_attr_ref_with(select, attr, &Extraction::default())
+    }
+
+    fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        let mut ret = vec![];
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_inner_text_ref_with(&mut vec![elem].into_iter(), extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
+        }
+        Ok(ret)
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        let mut ret = vec![];
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_attr_ref_with(&mut vec![elem].into_iter(), attr, extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
+        }
+        Ok(ret)
+    }
+}
+
+impl<'a, T> FromSourceRef<'a> for Vec<T>
+where
+    T: FromSourceRef<'a>,
+{
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        Self::from_source_ref_with(select, source, &Extraction::default())
+    }
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        let mut ret = vec![];
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_source_ref_with(&mut vec![elem].into_iter(), source, extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
+        }
+        Ok(ret)
+    }
+}
+
+impl<'a, T> FromHtmlRef<'a> for Vec<T>
+where
+    T: FromHtmlRef<'a>,
+{
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_elements_ref_with(select, &Extraction::default())
+    }
+
+    fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_elements_ref_report_with(select, &Extraction::default())
+    }
+
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        let mut ret = vec![];
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_elements_ref_with(&mut vec![elem].into_iter(), extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
+        }
+        Ok(ret)
+    }
+
+    fn from_elements_ref_report_with(
+        select: ElemIter<'_, 'a>,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        let mut ret = vec![];
+        let mut errors = vec![];
+        for (index, elem) in select.enumerate() {
+            match T::from_elements_ref_report_with(&mut vec![elem].into_iter(), extraction) {
+                Ok(item) => ret.push(item),
+                Err(err) => errors.push(err.at_index(index, &elem)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(ret)
+        } else {
+            Err(Error::multiple(errors))
+        }
+    }
+}
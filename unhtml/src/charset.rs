@@ -0,0 +1,208 @@
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use std::borrow::Cow;
+
+/// lowercase name and value of an attribute
+type Attribute = (Vec<u8>, Vec<u8>);
+
+/// bytes searched for a `<meta>` declaring the charset, like browsers
+const PRESCAN_LIMIT: usize = 1024;
+
+/// decode html bytes by the first charset found in:
+///
+/// 1. the byte order mark
+/// 2. `hint` from the transport layer, a charset label like `Shift_JIS`
+///    or a `Content-Type` like `text/html; charset=gbk`
+/// 3. `<meta charset="...">` or `<meta http-equiv="Content-Type" content="...">` in the first 1024 bytes
+///
+/// Without any of them, bytes are decoded as UTF-8 if they're valid, or Windows-1252 otherwise.
+/// Malformed sequences are replaced by `U+FFFD`.
+///
+/// ```rust
+/// let bytes = b"<meta charset=\"windows-1252\"><p>caf\xe9</p>";
+/// assert_eq!("<meta charset=\"windows-1252\"><p>café</p>", unhtml::decode_html(bytes, None));
+/// ```
+pub fn decode_html<'b>(bytes: &'b [u8], hint: Option<&str>) -> Cow<'b, str> {
+    let (encoding, bytes) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom)) => (encoding, &bytes[bom..]),
+        None => {
+            let encoding = hint
+                .and_then(hinted_encoding)
+                .or_else(|| prescan(&bytes[..bytes.len().min(PRESCAN_LIMIT)]))
+                .unwrap_or_else(|| match std::str::from_utf8(bytes) {
+                    Ok(_) => UTF_8,
+                    Err(_) => WINDOWS_1252,
+                });
+            (encoding, bytes)
+        }
+    };
+    encoding.decode_without_bom_handling(bytes).0
+}
+
+/// encoding of a charset label or a `Content-Type`
+fn hinted_encoding(hint: &str) -> Option<&'static Encoding> {
+    match charset_of_content(hint.as_bytes()) {
+        Some(label) => Encoding::for_label(label),
+        None => Encoding::for_label(hint.as_bytes()),
+    }
+}
+
+/// html declared in utf-16 is already decoded as bytes, so it can only be utf-8
+fn declared_encoding(label: &[u8]) -> Option<&'static Encoding> {
+    let encoding = Encoding::for_label(label)?;
+    Some(match encoding.name() {
+        "UTF-16LE" | "UTF-16BE" => UTF_8,
+        "x-user-defined" => WINDOWS_1252,
+        _ => encoding,
+    })
+}
+
+/// encoding declared by the first `<meta>` with a charset, skipping comments
+fn prescan(bytes: &[u8]) -> Option<&'static Encoding> {
+    let mut position = 0;
+    while position < bytes.len() {
+        let rest = &bytes[position..];
+        if rest.starts_with(b"<!--") {
+            position += find(rest, b"-->").map_or(rest.len(), |end| end + 3);
+        } else if starts_with_ignore_case(rest, b"<meta")
+            && rest
+                .get(5)
+                .is_some_and(|c| c.is_ascii_whitespace() || *c == b'/')
+        {
+            let (attrs, end) = attributes(&rest[5..]);
+            position += 5 + end;
+            if let Some(encoding) = meta_encoding(&attrs) {
+                return Some(encoding);
+            }
+        } else {
+            position += 1;
+        }
+    }
+    None
+}
+
+fn meta_encoding(attrs: &[Attribute]) -> Option<&'static Encoding> {
+    let attr = |name: &[u8]| {
+        attrs
+            .iter()
+            .find(|(attr, _)| attr.as_slice() == name)
+            .map(|(_, value)| value.as_slice())
+    };
+    if let Some(charset) = attr(b"charset") {
+        return declared_encoding(trim(charset));
+    }
+    let http_equiv = attr(b"http-equiv")?;
+    if !http_equiv.eq_ignore_ascii_case(b"content-type") {
+        return None;
+    }
+    declared_encoding(charset_of_content(attr(b"content")?)?)
+}
+
+/// the `charset` parameter of a `Content-Type`
+fn charset_of_content(content: &[u8]) -> Option<&[u8]> {
+    let lower = content.to_ascii_lowercase();
+    let start = find(&lower, b"charset")? + 7;
+    let value = trim_start(&content[start..]);
+    let value = trim_start(value.strip_prefix(b"=")?);
+    let value = match value.first() {
+        Some(quote @ (b'"' | b'\'')) => {
+            let value = &value[1..];
+            &value[..value.iter().position(|c| c == quote)?]
+        }
+        _ => {
+            let end = value
+                .iter()
+                .position(|c| c.is_ascii_whitespace() || *c == b';')
+                .unwrap_or(value.len());
+            &value[..end]
+        }
+    };
+    Some(value).filter(|value| !value.is_empty())
+}
+
+/// lowercase attributes of a tag until `>`, and the length parsed
+fn attributes(bytes: &[u8]) -> (Vec<Attribute>, usize) {
+    let mut attrs = Vec::new();
+    let mut position = 0;
+    loop {
+        while bytes
+            .get(position)
+            .is_some_and(|c| c.is_ascii_whitespace() || *c == b'/')
+        {
+            position += 1;
+        }
+        match bytes.get(position) {
+            None => return (attrs, position),
+            Some(b'>') => return (attrs, position + 1),
+            _ => (),
+        }
+        let name_start = position;
+        while bytes
+            .get(position)
+            .is_some_and(|c| !c.is_ascii_whitespace() && !matches!(c, b'=' | b'>' | b'/'))
+        {
+            position += 1;
+        }
+        let name = bytes[name_start..position].to_ascii_lowercase();
+        while bytes.get(position).is_some_and(u8::is_ascii_whitespace) {
+            position += 1;
+        }
+        let mut value = Vec::new();
+        if bytes.get(position) == Some(&b'=') {
+            position += 1;
+            while bytes.get(position).is_some_and(u8::is_ascii_whitespace) {
+                position += 1;
+            }
+            match bytes.get(position) {
+                Some(quote @ (b'"' | b'\'')) => {
+                    let start = position + 1;
+                    let end = bytes[start..]
+                        .iter()
+                        .position(|c| c == quote)
+                        .map_or(bytes.len(), |end| start + end);
+                    value = bytes[start..end].to_vec();
+                    position = (end + 1).min(bytes.len());
+                }
+                _ => {
+                    let start = position;
+                    while bytes
+                        .get(position)
+                        .is_some_and(|c| !c.is_ascii_whitespace() && *c != b'>')
+                    {
+                        position += 1;
+                    }
+                    value = bytes[start..position].to_vec();
+                }
+            }
+        }
+        if !attrs.iter().any(|(attr, _)| *attr == name) {
+            attrs.push((name, value));
+        }
+    }
+}
+
+fn find(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    bytes
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+}
+
+fn starts_with_ignore_case(bytes: &[u8], prefix: &[u8]) -> bool {
+    bytes.len() >= prefix.len() && bytes[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|c| !c.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn trim(bytes: &[u8]) -> &[u8] {
+    let bytes = trim_start(bytes);
+    let end = bytes
+        .iter()
+        .rposition(|c| !c.is_ascii_whitespace())
+        .map_or(0, |end| end + 1);
+    &bytes[..end]
+}
@@ -0,0 +1,349 @@
+use crate::{
+    ElemIter, Error, Extraction, FromHtml, FromHtmlRef, FromSource, FromSourceRef, FromText,
+    FromTextRef, Result, Source,
+};
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use std::convert::TryInto;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// collections built from all selected elements like `Vec<T>`
+macro_rules! from_collection {
+    ($(($collection:ident $(, $bound:path)*)),*) => {
+        $(
+            impl<T> FromHtml for $collection<T>
+            where
+                T: FromHtml $(+ $bound)*,
+            {
+                fn from_elements(select: ElemIter) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements(select)?.into_iter().collect())
+                }
+
+                fn from_elements_report(select: ElemIter) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_report(select)?.into_iter().collect())
+                }
+
+                fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_with(select, extraction)?.into_iter().collect())
+                }
+
+                fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_report_with(select, extraction)?.into_iter().collect())
+                }
+            }
+
+            impl<T> FromText for $collection<T>
+            where
+                T: FromText $(+ $bound)*,
+            {
+                fn from_inner_text(select: ElemIter) -> Result<Self> {
+                    Ok(Vec::<T>::from_inner_text(select)?.into_iter().collect())
+                }
+
+                fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+                    Ok(Vec::<T>::from_attr(select, attr)?.into_iter().collect())
+                }
+
+                fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_inner_text_with(select, extraction)?.into_iter().collect())
+                }
+
+                fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_attr_with(select, attr, extraction)?.into_iter().collect())
+                }
+            }
+
+            impl<T> FromSource for $collection<T>
+            where
+                T: FromSource $(+ $bound)*,
+            {
+                fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+                    Ok(Vec::<T>::from_source(select, source)?.into_iter().collect())
+                }
+
+                fn from_source_with(select: ElemIter, source: &Source, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_source_with(select, source, extraction)?.into_iter().collect())
+                }
+            }
+
+            impl<'a, T> FromHtmlRef<'a> for $collection<T>
+            where
+                T: FromHtmlRef<'a> $(+ $bound)*,
+            {
+                fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_ref(select)?.into_iter().collect())
+                }
+
+                fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_ref_report(select)?.into_iter().collect())
+                }
+
+                fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_ref_with(select, extraction)?.into_iter().collect())
+                }
+
+                fn from_elements_ref_report_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_elements_ref_report_with(select, extraction)?.into_iter().collect())
+                }
+            }
+
+            impl<'a, T> FromTextRef<'a> for $collection<T>
+            where
+                T: FromTextRef<'a> $(+ $bound)*,
+            {
+                fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    Ok(Vec::<T>::from_inner_text_ref(select)?.into_iter().collect())
+                }
+
+                fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+                    Ok(Vec::<T>::from_attr_ref(select, attr)?.into_iter().collect())
+                }
+
+                fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_inner_text_ref_with(select, extraction)?.into_iter().collect())
+                }
+
+                fn from_attr_ref_with(select: ElemIter<'_, 'a>, attr: &str, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_attr_ref_with(select, attr, extraction)?.into_iter().collect())
+                }
+            }
+
+            impl<'a, T> FromSourceRef<'a> for $collection<T>
+            where
+                T: FromSourceRef<'a> $(+ $bound)*,
+            {
+                fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+                    Ok(Vec::<T>::from_source_ref(select, source)?.into_iter().collect())
+                }
+
+                fn from_source_ref_with(select: ElemIter<'_, 'a>, source: &Source, extraction: &Extraction) -> Result<Self> {
+                    Ok(Vec::<T>::from_source_ref_with(select, source, extraction)?.into_iter().collect())
+                }
+            }
+        )*
+    };
+}
+
+from_collection!(
+    (VecDeque),
+    (LinkedList),
+    (HashSet, Eq, Hash),
+    (BTreeSet, Ord)
+);
+
+/// smart pointers built from the first selected element like `T`
+macro_rules! from_pointer {
+    ($($pointer:ident),*) => {
+        $(
+            impl<T: FromHtml> FromHtml for $pointer<T> {
+                fn from_elements(select: ElemIter) -> Result<Self> {
+                    T::from_elements(select).map($pointer::new)
+                }
+
+                fn from_elements_report(select: ElemIter) -> Result<Self> {
+                    T::from_elements_report(select).map($pointer::new)
+                }
+
+                fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    T::from_elements_with(select, extraction).map($pointer::new)
+                }
+
+                fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    T::from_elements_report_with(select, extraction).map($pointer::new)
+                }
+            }
+
+            impl<T: FromText> FromText for $pointer<T> {
+                fn from_inner_text(select: ElemIter) -> Result<Self> {
+                    T::from_inner_text(select).map($pointer::new)
+                }
+
+                fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+                    T::from_attr(select, attr).map($pointer::new)
+                }
+
+                fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+                    T::from_inner_text_with(select, extraction).map($pointer::new)
+                }
+
+                fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+                    T::from_attr_with(select, attr, extraction).map($pointer::new)
+                }
+            }
+
+            impl<T: FromSource> FromSource for $pointer<T> {
+                fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+                    T::from_source(select, source).map($pointer::new)
+                }
+
+                fn from_source_with(select: ElemIter, source: &Source, extraction: &Extraction) -> Result<Self> {
+                    T::from_source_with(select, source, extraction).map($pointer::new)
+                }
+            }
+
+            impl<'a, T: FromHtmlRef<'a>> FromHtmlRef<'a> for $pointer<T> {
+                fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    T::from_elements_ref(select).map($pointer::new)
+                }
+
+                fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    T::from_elements_ref_report(select).map($pointer::new)
+                }
+
+                fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    T::from_elements_ref_with(select, extraction).map($pointer::new)
+                }
+
+                fn from_elements_ref_report_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    T::from_elements_ref_report_with(select, extraction).map($pointer::new)
+                }
+            }
+
+            impl<'a, T: FromTextRef<'a>> FromTextRef<'a> for $pointer<T> {
+                fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    T::from_inner_text_ref(select).map($pointer::new)
+                }
+
+                fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+                    T::from_attr_ref(select, attr).map($pointer::new)
+                }
+
+                fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+                    T::from_inner_text_ref_with(select, extraction).map($pointer::new)
+                }
+
+                fn from_attr_ref_with(select: ElemIter<'_, 'a>, attr: &str, extraction: &Extraction) -> Result<Self> {
+                    T::from_attr_ref_with(select, attr, extraction).map($pointer::new)
+                }
+            }
+
+            impl<'a, T: FromSourceRef<'a>> FromSourceRef<'a> for $pointer<T> {
+                fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+                    T::from_source_ref(select, source).map($pointer::new)
+                }
+
+                fn from_source_ref_with(select: ElemIter<'_, 'a>, source: &Source, extraction: &Extraction) -> Result<Self> {
+                    T::from_source_ref_with(select, source, extraction).map($pointer::new)
+                }
+            }
+        )*
+    };
+}
+
+from_pointer!(Box, Rc, Arc);
+
+/// exactly `N` selected elements
+fn to_array<T, const N: usize>(items: Vec<T>) -> Result<[T; N]> {
+    let found = items.len();
+    items
+        .try_into()
+        .map_err(|_| Error::CountMismatch { expected: N, found })
+}
+
+impl<T: FromHtml, const N: usize> FromHtml for [T; N] {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        to_array(Vec::from_elements(select)?)
+    }
+
+    fn from_elements_report(select: ElemIter) -> Result<Self> {
+        to_array(Vec::from_elements_report(select)?)
+    }
+
+    fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_elements_with(select, extraction)?)
+    }
+
+    fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_elements_report_with(select, extraction)?)
+    }
+}
+
+impl<T: FromText, const N: usize> FromText for [T; N] {
+    fn from_inner_text(select: ElemIter) -> Result<Self> {
+        to_array(Vec::from_inner_text(select)?)
+    }
+
+    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+        to_array(Vec::from_attr(select, attr)?)
+    }
+
+    fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_inner_text_with(select, extraction)?)
+    }
+
+    fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_attr_with(select, attr, extraction)?)
+    }
+}
+
+impl<T: FromSource, const N: usize> FromSource for [T; N] {
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        to_array(Vec::from_source(select, source)?)
+    }
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        to_array(Vec::from_source_with(select, source, extraction)?)
+    }
+}
+
+impl<'a, T: FromHtmlRef<'a>, const N: usize> FromHtmlRef<'a> for [T; N] {
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        to_array(Vec::from_elements_ref(select)?)
+    }
+
+    fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+        to_array(Vec::from_elements_ref_report(select)?)
+    }
+
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_elements_ref_with(select, extraction)?)
+    }
+
+    fn from_elements_ref_report_with(
+        select: ElemIter<'_, 'a>,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        to_array(Vec::from_elements_ref_report_with(select, extraction)?)
+    }
+}
+
+impl<'a, T: FromTextRef<'a>, const N: usize> FromTextRef<'a> for [T; N] {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        to_array(Vec::from_inner_text_ref(select)?)
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        to_array(Vec::from_attr_ref(select, attr)?)
+    }
+
+    fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        to_array(Vec::from_inner_text_ref_with(select, extraction)?)
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        to_array(Vec::from_attr_ref_with(select, attr, extraction)?)
+    }
+}
+
+impl<'a, T: FromSourceRef<'a>, const N: usize> FromSourceRef<'a> for [T; N] {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        to_array(Vec::from_source_ref(select, source)?)
+    }
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        to_array(Vec::from_source_ref_with(select, source, extraction)?)
+    }
+}
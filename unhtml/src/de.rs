@@ -0,0 +1,481 @@
+//! extract any `Deserialize` type from html, as an alternative to `FromHtml`
+//!
+//! Each field of a struct is looked up by a key, which is the field name after `#[serde(rename = "...")]`
+//! or the key given to `FieldMap` for the field:
+//!
+//! - `selector`: elements selected by the css selector, like `span.price`
+//! - `selector@attr`: the attribute of elements selected by the selector, like `a@href`
+//! - `@attr`: the attribute of the current elements
+//! - `.`: the current elements
+//!
+//! Scalars like numbers and strings are parsed from the trimmed text or attribute of the first element,
+//! sequences take every element, and unit enum variants are matched by the text.
+//!
+//! A field whose key selects nothing, or whose attribute is missing, is left out of the struct,
+//! so `Option` fields are `None`, `#[serde(default)]` fields take their default
+//! and other fields fail with `SourceNotFound`.
+//! Give `Vec` fields `#[serde(default)]` to allow an empty selection.
+//!
+//! ```rust
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Link {
+//!     #[serde(rename = "@href")]
+//!     href: String,
+//!     #[serde(rename = ".")]
+//!     text: String,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Page {
+//!     title: String,
+//!     #[serde(default, rename = "a")]
+//!     links: Vec<Link>,
+//! }
+//!
+//! let page: Page = unhtml::de::from_html(
+//!     r#"<title>Links</title><a href="https://github.com">Github</a><a href="/">Home</a>"#,
+//! )
+//! .unwrap();
+//! assert_eq!("Links", page.title);
+//! assert_eq!("https://github.com", page.links[0].href);
+//! assert_eq!("Home", page.links[1].text);
+//! ```
+
+use crate::borrow::{borrow_attr, borrow_text};
+use crate::{BoolVocabulary, Error, PathSegment, Result};
+use scraper::{ElementRef, Html, Selector};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::iter;
+use std::rc::Rc;
+
+/// keys of fields given aside from the types, instead of `#[serde(rename = "...")]`
+///
+/// ```rust
+/// use serde::Deserialize;
+/// use unhtml::de::FieldMap;
+///
+/// #[derive(Deserialize)]
+/// struct Product {
+///     name: String,
+///     price: f64,
+/// }
+///
+/// let fields = FieldMap::new()
+///     .field("Product", "name", "h2")
+///     .field("Product", "price", ".price@data-value");
+/// let product: Product = unhtml::de::from_html_with(
+///     r#"<h2>Tea</h2><span class="price" data-value="1.5">$1.50</span>"#,
+///     &fields,
+/// )
+/// .unwrap();
+/// assert_eq!("Tea", product.name);
+/// assert_eq!(1.5, product.price);
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct FieldMap {
+    keys: HashMap<(String, String), String>,
+}
+
+impl FieldMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// look up `field` of struct `struct_name` by `key`, names are the ones seen by serde
+    pub fn field(mut self, struct_name: &str, field: &str, key: &str) -> Self {
+        self.keys
+            .insert((struct_name.to_owned(), field.to_owned()), key.to_owned());
+        self
+    }
+
+    fn key(&self, struct_name: &str, field: &str) -> Option<&str> {
+        self.keys
+            .get(&(struct_name.to_owned(), field.to_owned()))
+            .map(String::as_str)
+    }
+}
+
+/// key of `field`, from `fields` or the field name
+fn field_key<'m>(fields: Option<&'m FieldMap>, struct_name: &str, field: &'m str) -> &'m str {
+    fields
+        .and_then(|fields| fields.key(struct_name, field))
+        .unwrap_or(field)
+}
+
+/// a serde deserializer over selected elements
+pub struct Deserializer<'a, 'm> {
+    elements: Vec<ElementRef<'a>>,
+    attr: Option<&'m str>,
+    fields: Option<&'m FieldMap>,
+    /// selectors parsed by key, shared by every nested deserializer
+    selectors: Rc<RefCell<HashMap<&'m str, Selector>>>,
+}
+
+impl<'a, 'm> Deserializer<'a, 'm> {
+    pub fn new(elements: impl IntoIterator<Item = ElementRef<'a>>) -> Self {
+        Self {
+            elements: elements.into_iter().collect(),
+            attr: None,
+            fields: None,
+            selectors: Rc::default(),
+        }
+    }
+
+    /// look up fields by `fields` first
+    pub fn with_fields(mut self, fields: &'m FieldMap) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// trimmed text or attribute of the first element
+    fn text(&self) -> Result<Cow<'a, str>> {
+        let mut first = self.elements.first().copied().into_iter();
+        match self.attr {
+            Some(attr) => borrow_attr(&mut first, attr).map(Cow::Borrowed),
+            None => borrow_text(&mut first),
+        }
+    }
+
+    fn parse<T>(&self, type_name: &str) -> Result<T>
+    where
+        T: std::str::FromStr,
+        T::Err: Display,
+    {
+        let text = self.text()?;
+        text.parse().map_err(|err: T::Err| {
+            (text.into_owned(), type_name.to_owned(), err.to_string()).into()
+        })
+    }
+
+    /// nothing is selected or the attribute is missing
+    fn is_missing(&self) -> bool {
+        match (self.elements.first(), self.attr) {
+            (None, _) => true,
+            (Some(first), Some(attr)) => first.value().attr(attr).is_none(),
+            (Some(_), None) => false,
+        }
+    }
+
+    /// the deserializer of a field looked up by `key`
+    fn select(&self, key: &'m str) -> Result<Self> {
+        let (selector, attr) = split_key(key);
+        let elements = match selector {
+            Some(selector) => {
+                let mut selectors = self.selectors.borrow_mut();
+                let selector = match selectors.get(key) {
+                    Some(selector) => selector,
+                    None => {
+                        let parsed = Selector::parse(selector).map_err(|err| {
+                            Error::Custom(format!("invalid css selector `{}`: {:?}", selector, err))
+                        })?;
+                        selectors.entry(key).or_insert(parsed)
+                    }
+                };
+                self.elements
+                    .iter()
+                    .flat_map(|elem| elem.select(selector))
+                    .collect()
+            }
+            None => self.elements.clone(),
+        };
+        Ok(Self {
+            elements,
+            attr,
+            fields: self.fields,
+            selectors: self.selectors.clone(),
+        })
+    }
+}
+
+/// selector and attribute of a key like `a@href`
+fn split_key(key: &str) -> (Option<&str>, Option<&str>) {
+    let (selector, attr) = match key.rfind('@') {
+        Some(at) if !key[at + 1..].contains([']', '"', '\'']) => (&key[..at], Some(&key[at + 1..])),
+        _ => (key, None),
+    };
+    match selector.trim() {
+        "" | "." => (None, attr),
+        selector => (Some(selector), attr),
+    }
+}
+
+/// parse `html` as a document and deserialize `T` from its root element
+pub fn from_html<T: DeserializeOwned>(html: &str) -> Result<T> {
+    let document = Html::parse_document(html);
+    from_document(&document)
+}
+
+/// like `from_html`, but look up fields by `fields` first
+pub fn from_html_with<T: DeserializeOwned>(html: &str, fields: &FieldMap) -> Result<T> {
+    let document = Html::parse_document(html);
+    T::deserialize(Deserializer::new(iter::once(document.root_element())).with_fields(fields))
+}
+
+/// deserialize `T` borrowing from `document`, like `&str` fields
+pub fn from_document<'a, T: Deserialize<'a>>(document: &'a Html) -> Result<T> {
+    T::deserialize(Deserializer::new(iter::once(document.root_element())))
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+
+    /// the struct name and selector are filled in by `deserialize_struct`
+    fn missing_field(field: &'static str) -> Self {
+        Error::SourceNotFound.in_field("", field, None, &[])
+    }
+}
+
+macro_rules! deserialize_from_str {
+    ($($method:ident => $visit:ident($typ:ty)),*) => {
+        $(
+            fn $method<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+                visitor.$visit(self.parse::<$typ>(stringify!($typ))?)
+            }
+        )*
+    };
+}
+
+impl<'a, 'm> de::Deserializer<'a> for Deserializer<'a, 'm> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        if self.elements.is_empty() {
+            return visitor.visit_none();
+        }
+        self.deserialize_str(visitor)
+    }
+
+    deserialize_from_str!(
+        deserialize_i8 => visit_i8(i8),
+        deserialize_i16 => visit_i16(i16),
+        deserialize_i32 => visit_i32(i32),
+        deserialize_i64 => visit_i64(i64),
+        deserialize_i128 => visit_i128(i128),
+        deserialize_u8 => visit_u8(u8),
+        deserialize_u16 => visit_u16(u16),
+        deserialize_u32 => visit_u32(u32),
+        deserialize_u64 => visit_u64(u64),
+        deserialize_u128 => visit_u128(u128),
+        deserialize_f32 => visit_f32(f32),
+        deserialize_f64 => visit_f64(f64),
+        deserialize_char => visit_char(char)
+    );
+
+    fn deserialize_bool<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_bool(BoolVocabulary::DEFAULT.parse(&self.text()?)?)
+    }
+
+    fn deserialize_str<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        match self.text()? {
+            Cow::Borrowed(text) => visitor.visit_borrowed_str(text),
+            Cow::Owned(text) => visitor.visit_string(text),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        match self.text()? {
+            Cow::Borrowed(text) => visitor.visit_borrowed_bytes(text.as_bytes()),
+            Cow::Owned(text) => visitor.visit_byte_buf(text.into_bytes()),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        if self.is_missing() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Items {
+            elements: self.elements.into_iter().enumerate(),
+            attr: self.attr,
+            fields: self.fields,
+            selectors: self.selectors,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'a>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'a>>(self, _visitor: V) -> Result<V::Value> {
+        Err(de::Error::custom(
+            "maps cannot be deserialized from html, use a struct instead",
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'a>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if self.elements.is_empty() {
+            return Err(Error::SourceNotFound);
+        }
+        let (elements, field_map) = (self.elements.clone(), self.fields);
+        visitor
+            .visit_map(Fields {
+                de: self,
+                struct_name: name,
+                fields: fields.iter(),
+                current: None,
+            })
+            .map_err(|err| match err {
+                // raised by `missing_field` for a field left out by `next_key_seed`
+                Error::FieldError { context, source } if context.struct_name.is_empty() => {
+                    match context.path.first() {
+                        Some(PathSegment::Field(field)) => {
+                            let (selector, _) = split_key(field_key(field_map, name, field));
+                            source.in_field(name, field, selector, &elements)
+                        }
+                        _ => Error::FieldError { context, source },
+                    }
+                }
+                err => err,
+            })
+    }
+
+    /// unit variants matched by the text
+    fn deserialize_enum<V: Visitor<'a>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self.text()?.into_owned().into_deserializer())
+    }
+
+    fn deserialize_identifier<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// each field of a struct looked up by its key,
+/// fields selecting nothing are skipped so serde treats them as missing
+struct Fields<'a, 'm> {
+    de: Deserializer<'a, 'm>,
+    struct_name: &'static str,
+    fields: std::slice::Iter<'static, &'static str>,
+    current: Option<(&'static str, Deserializer<'a, 'm>)>,
+}
+
+impl<'a, 'm> Fields<'a, 'm> {
+    fn in_field(&self, err: Error, field: &str) -> Error {
+        let (selector, _) = split_key(field_key(self.de.fields, self.struct_name, field));
+        err.in_field(self.struct_name, field, selector, &self.de.elements)
+    }
+}
+
+impl<'a, 'm> MapAccess<'a> for Fields<'a, 'm> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'a>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        while let Some(&field) = self.fields.next() {
+            let de = self
+                .de
+                .select(field_key(self.de.fields, self.struct_name, field))
+                .map_err(|err| self.in_field(err, field))?;
+            if !de.is_missing() {
+                self.current = Some((field, de));
+                return seed.deserialize(field.into_deserializer()).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'a>>(&mut self, seed: V) -> Result<V::Value> {
+        let (field, de) = self.current.take().ok_or(Error::SourceNotFound)?;
+        seed.deserialize(de)
+            .map_err(|err| self.in_field(err, field))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len())
+    }
+}
+
+/// each element of a sequence
+struct Items<'a, 'm> {
+    elements: iter::Enumerate<std::vec::IntoIter<ElementRef<'a>>>,
+    attr: Option<&'m str>,
+    fields: Option<&'m FieldMap>,
+    selectors: Rc<RefCell<HashMap<&'m str, Selector>>>,
+}
+
+impl<'a, 'm> SeqAccess<'a> for Items<'a, 'm> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'a>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        match self.elements.next() {
+            Some((index, elem)) => seed
+                .deserialize(Deserializer {
+                    elements: vec![elem],
+                    attr: self.attr,
+                    fields: self.fields,
+                    selectors: self.selectors.clone(),
+                })
+                .map(Some)
+                .map_err(|err| err.at_index(index, &elem)),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.elements.len())
+    }
+}
@@ -1,6 +1,12 @@
 use derive_more::{Display, From};
+use html5ever::serialize::{serialize, SerializeOpts, TraversalScope};
+use scraper::ElementRef;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+
+/// max chars of element html kept in a `Context`
+const SNIPPET_LIMIT: usize = 128;
 
-// TODO: SourceNotFound should contain selector info
 #[derive(Display, Debug, From)]
 pub enum Error {
     #[display(fmt = "source not found")]
@@ -13,8 +19,255 @@ pub enum Error {
         type_name: String,
         err: String,
     },
+    #[display(fmt = "expected {} elements, found {}", expected, found)]
+    #[from(ignore)]
+    CountMismatch { expected: usize, found: usize },
+    #[display(fmt = "duplicate key")]
+    #[from(ignore)]
+    DuplicateKey,
+    #[display(fmt = "{}: {}", context, source)]
+    #[from(ignore)]
+    FieldError {
+        context: Box<Context>,
+        source: Box<Error>,
+    },
+    #[display(
+        fmt = "no variant of {} matched: {}",
+        enum_name,
+        "join_variants(errors)"
+    )]
+    #[from(ignore)]
+    NoVariantMatched {
+        enum_name: String,
+        errors: Vec<(String, Error)>,
+    },
+    #[display(fmt = "{} errors: {}", "_0.len()", "join(_0)")]
+    #[from(ignore)]
+    Multiple(Vec<Error>),
+    #[display(fmt = "{}", _0)]
+    #[from(ignore)]
+    Custom(String),
+    #[display(fmt = "{}", _0)]
+    Io(std::io::Error),
+}
+
+/// where a `FieldError` was raised
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Context {
+    /// name of the outermost derived struct
+    pub struct_name: String,
+    /// path from `struct_name` to the failed field, like `.links[3].href`
+    pub path: Vec<PathSegment>,
+    /// css selector of the failed field, `None` if the field has no selector
+    pub selector: Option<String>,
+    /// bounded excerpt of the element the selector was evaluated against
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl Display for PathSegment {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PathSegment::Field(name) => write!(f, ".{}", name),
+            PathSegment::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+impl Context {
+    /// dotted field path, like `Websites.links[3].href`
+    pub fn field_path(&self) -> String {
+        let mut path = self.struct_name.clone();
+        for segment in &self.path {
+            path += &segment.to_string();
+        }
+        path
+    }
+}
+
+impl Display for Context {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "`{}`", self.field_path())?;
+        if let Some(selector) = &self.selector {
+            write!(f, " selected by `{}`", selector)?;
+        }
+        if !self.snippet.is_empty() {
+            write!(f, " in `{}`", self.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error {
+    /// aggregate errors, nested `Multiple` are flattened and a single error is returned as is
+    pub fn multiple(errors: Vec<Error>) -> Self {
+        let mut flattened = Vec::with_capacity(errors.len());
+        for err in errors {
+            match err {
+                Error::Multiple(inner) => flattened.extend(inner),
+                err => flattened.push(err),
+            }
+        }
+        if flattened.len() == 1 {
+            flattened.remove(0)
+        } else {
+            Error::Multiple(flattened)
+        }
+    }
+
+    /// attach the field of a derived struct to this error,
+    /// `elements` are the elements the field selector was evaluated against
+    pub fn in_field(
+        self,
+        struct_name: &str,
+        field: &str,
+        selector: Option<&str>,
+        elements: &[ElementRef],
+    ) -> Self {
+        if let Error::Multiple(errors) = self {
+            return Error::Multiple(
+                errors
+                    .into_iter()
+                    .map(|err| err.in_field(struct_name, field, selector, elements))
+                    .collect(),
+            );
+        }
+        let (mut context, source) = self.into_context();
+        context.struct_name = struct_name.to_owned();
+        context.path.insert(0, PathSegment::Field(field.to_owned()));
+        if context.selector.is_none() {
+            context.selector = selector.map(ToOwned::to_owned);
+        }
+        if context.snippet.is_empty() {
+            context.snippet = elements.first().map(snippet).unwrap_or_default();
+        }
+        Error::FieldError {
+            context: Box::new(context),
+            source,
+        }
+    }
+
+    /// attach the index of a collection item to this error
+    pub fn at_index(self, index: usize, element: &ElementRef) -> Self {
+        if let Error::Multiple(errors) = self {
+            return Error::Multiple(
+                errors
+                    .into_iter()
+                    .map(|err| err.at_index(index, element))
+                    .collect(),
+            );
+        }
+        let (mut context, source) = self.into_context();
+        context.path.insert(0, PathSegment::Index(index));
+        if context.snippet.is_empty() {
+            context.snippet = snippet(element);
+        }
+        Error::FieldError {
+            context: Box::new(context),
+            source,
+        }
+    }
+
+    /// whether this error itself is `SourceNotFound` or `AttrNotFound`,
+    /// errors wrapped in a `FieldError` are not taken into account
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::SourceNotFound | Error::AttrNotFound { .. })
+    }
+
+    /// the context of a `FieldError`
+    pub fn context(&self) -> Option<&Context> {
+        match self {
+            Error::FieldError { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// the error without any `FieldError` wrapping it
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::FieldError { source, .. } => source.root_cause(),
+            err => err,
+        }
+    }
+
+    fn into_context(self) -> (Context, Box<Error>) {
+        match self {
+            Error::FieldError { context, source } => (*context, source),
+            err => (
+                Context {
+                    struct_name: String::new(),
+                    path: Vec::new(),
+                    selector: None,
+                    snippet: String::new(),
+                },
+                Box::new(err),
+            ),
+        }
+    }
+}
+
+fn join(errors: &[Error]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn join_variants(errors: &[(String, Error)]) -> String {
+    errors
+        .iter()
+        .map(|(variant, err)| format!("`{}`: {}", variant, err))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// html of `element`, serialized only up to the first `SNIPPET_LIMIT` chars
+fn snippet(element: &ElementRef) -> String {
+    let opts = SerializeOpts {
+        scripting_enabled: false,
+        traversal_scope: TraversalScope::IncludeNode,
+        create_missing_parent: false,
+    };
+    let mut buf = SnippetBuf(Vec::new());
+    // serialization is stopped by `SnippetBuf` once it's full
+    let _ = serialize(&mut buf, element, opts);
+    let html = String::from_utf8_lossy(&buf.0);
+    match html.char_indices().nth(SNIPPET_LIMIT) {
+        Some((end, _)) => format!("{}...", &html[..end]),
+        None => html.into_owned(),
+    }
+}
+
+/// a buffer failing writes after more than `SNIPPET_LIMIT` chars, 4 bytes at most each
+struct SnippetBuf(Vec<u8>);
+
+impl Write for SnippetBuf {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        if self.0.len() > SNIPPET_LIMIT * 4 {
+            return Err(io::Error::new(io::ErrorKind::Other, "snippet is full"));
+        }
+        self.0.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::FieldError { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
 pub type Result<T> = std::result::Result<T, Error>;
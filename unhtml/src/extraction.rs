@@ -0,0 +1,60 @@
+#[cfg(feature = "url")]
+use once_cell::unsync::OnceCell;
+#[cfg(feature = "url")]
+use scraper::{ElementRef, Node};
+#[cfg(feature = "url")]
+use url::Url;
+
+/// what a whole extraction shares, passed down to every field by the `*_with` methods
+///
+/// An extraction is made for one document by methods like `from_html_with_base`,
+/// or by `Extraction::default()` for methods without one.
+#[derive(Debug, Default)]
+pub struct Extraction {
+    /// base url given by the caller
+    #[cfg(feature = "url")]
+    base: Option<Url>,
+    /// `base` resolved by the `<base>` element, looked up once by the first `Url`
+    #[cfg(feature = "url")]
+    document_base: OnceCell<Option<Url>>,
+}
+
+impl Extraction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// resolve relative `Url` fields against `base`
+    #[cfg(feature = "url")]
+    pub fn with_base(mut self, base: Url) -> Self {
+        self.base = Some(base);
+        self.document_base = OnceCell::new();
+        self
+    }
+
+    /// base url of `element`'s document: the `href` of the first `<base>` element
+    /// resolved against the given base like browsers do, or taken as is if it's absolute
+    #[cfg(feature = "url")]
+    pub(crate) fn base_url(&self, element: ElementRef) -> Option<&Url> {
+        self.document_base
+            .get_or_init(|| {
+                let href =
+                    element
+                        .tree()
+                        .root()
+                        .descendants()
+                        .find_map(|node| match node.value() {
+                            Node::Element(elem) if elem.name() == "base" => elem.attr("href"),
+                            _ => None,
+                        });
+                match (&self.base, href) {
+                    (Some(base), Some(href)) => {
+                        Some(base.join(href).unwrap_or_else(|_| base.clone()))
+                    }
+                    (None, Some(href)) => Url::parse(href).ok(),
+                    (base, None) => base.clone(),
+                }
+            })
+            .as_ref()
+    }
+}
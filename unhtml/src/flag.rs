@@ -0,0 +1,76 @@
+use crate::Result;
+
+/// words parsed as `true` or `false`, compared case-insensitively after trimming
+///
+/// If only `truthy` is given, any other text is `false`; if only `falsy` is given, any other text is `true`.
+///
+/// ```rust
+/// use unhtml::BoolVocabulary;
+///
+/// const CHECK: BoolVocabulary = BoolVocabulary::new(&["✓"], &["✗"]);
+/// assert!(CHECK.parse("✓").unwrap());
+/// assert!(!CHECK.parse(" ✗ ").unwrap());
+/// assert!(CHECK.parse("?").is_err());
+/// assert!(BoolVocabulary::DEFAULT.parse("Yes").unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BoolVocabulary {
+    truthy: &'static [&'static str],
+    falsy: &'static [&'static str],
+}
+
+impl BoolVocabulary {
+    /// vocabulary used by `FromText for bool`
+    pub const DEFAULT: BoolVocabulary = BoolVocabulary::new(
+        &["true", "yes", "y", "on", "1", "✓", "✔"],
+        &["false", "no", "n", "off", "0", "✗", "✘"],
+    );
+
+    pub const fn new(truthy: &'static [&'static str], falsy: &'static [&'static str]) -> Self {
+        Self { truthy, falsy }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<bool> {
+        let key = text.trim().to_lowercase();
+        let matches = |words: &[&str]| words.iter().any(|word| word.to_lowercase() == key);
+        if matches(self.truthy) {
+            Ok(true)
+        } else if matches(self.falsy) || self.falsy.is_empty() {
+            Ok(false)
+        } else if self.truthy.is_empty() {
+            Ok(true)
+        } else {
+            let expected: Vec<_> = self
+                .truthy
+                .iter()
+                .chain(self.falsy)
+                .map(|word| format!("`{}`", word))
+                .collect();
+            Err((
+                text.trim().to_owned(),
+                "bool".to_owned(),
+                format!("expected one of {}", expected.join(", ")),
+            )
+                .into())
+        }
+    }
+}
+
+/// types of `truthy` and `falsy` fields, the text is matched by the vocabulary of the field
+///
+/// `Option<bool>` is `None` if the text is missing or matches no word, like other `Option<T>` fields.
+pub trait FromVocabulary: Sized {
+    fn from_vocabulary(text: Result<String>, vocabulary: &BoolVocabulary) -> Result<Self>;
+}
+
+impl FromVocabulary for bool {
+    fn from_vocabulary(text: Result<String>, vocabulary: &BoolVocabulary) -> Result<Self> {
+        vocabulary.parse(&text?)
+    }
+}
+
+impl<T: FromVocabulary> FromVocabulary for Option<T> {
+    fn from_vocabulary(text: Result<String>, vocabulary: &BoolVocabulary) -> Result<Self> {
+        Ok(T::from_vocabulary(text, vocabulary).ok())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{ElemIter, Extraction, Result};
+use scraper::ElementRef;
+use std::iter::{self, Enumerate};
+
+/// items extracted lazily from each selected element, like the items of `Vec<T>` one at a time
+///
+/// ```rust
+/// use unhtml::{scraper::{Html, Selector}, ElemIter, FromHtml, FromText};
+///
+/// struct Price(u32);
+///
+/// impl FromHtml for Price {
+///     fn from_elements(select: ElemIter) -> unhtml::Result<Self> {
+///         u32::from_inner_text(select).map(Price)
+///     }
+/// }
+///
+/// let document = Html::parse_document("<ul><li>1</li><li>2</li><li>free</li></ul>");
+/// let selector = Selector::parse("li").unwrap();
+/// let first_two: Vec<_> = Price::iter_html(&document, &selector)
+///     .take(2)
+///     .map(|price| price.unwrap().0)
+///     .collect();
+/// assert_eq!(vec![1, 2], first_two);
+/// ```
+pub struct Items<'b, 'a, T> {
+    elements: Enumerate<Box<dyn Iterator<Item = ElementRef<'a>> + 'b>>,
+    extract: fn(ElemIter<'_, 'a>, &Extraction) -> Result<T>,
+    extraction: Extraction,
+}
+
+impl<'b, 'a: 'b, T> Items<'b, 'a, T> {
+    /// extract each element in `elements` by `extract`, like `T::from_elements_with`
+    pub fn new(
+        elements: impl Iterator<Item = ElementRef<'a>> + 'b,
+        extract: fn(ElemIter<'_, 'a>, &Extraction) -> Result<T>,
+    ) -> Self {
+        let elements: Box<dyn Iterator<Item = ElementRef<'a>> + 'b> = Box::new(elements);
+        Self {
+            elements: elements.enumerate(),
+            extract,
+            extraction: Extraction::default(),
+        }
+    }
+
+    /// share `extraction` between all items instead of a default one
+    pub fn with_extraction(mut self, extraction: Extraction) -> Self {
+        self.extraction = extraction;
+        self
+    }
+}
+
+impl<'b, 'a: 'b, T> Iterator for Items<'b, 'a, T> {
+    type Item = Result<T>;
+    fn next(&mut self) -> Option<Result<T>> {
+        let (index, elem) = self.elements.next()?;
+        Some(
+            (self.extract)(&mut iter::once(elem), &self.extraction)
+                .map_err(|err| err.at_index(index, &elem)),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.elements.size_hint()
+    }
+}
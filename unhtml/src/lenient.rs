@@ -0,0 +1,231 @@
+use crate::{
+    ElemIter, Error, Extraction, FromHtml, FromHtmlRef, FromSource, FromSourceRef, FromText,
+    FromTextRef, Result, Source,
+};
+use scraper::ElementRef;
+use std::iter::{self, Once};
+use std::ops::Deref;
+
+/// items extracted from all selected elements like `Vec<T>`,
+/// but an item failing to extract is skipped instead of failing the whole list
+///
+/// ```rust
+/// use unhtml::{scraper::{Html, Selector}, Lenient, Text};
+///
+/// let document = Html::parse_fragment("<ul><li>1</li><li>two</li><li>3</li></ul>");
+/// let selector = Selector::parse("li").unwrap();
+/// let numbers: Lenient<u32> = document.select(&selector).inner_text().unwrap();
+/// assert_eq!(&[1, 3], numbers.items());
+/// assert_eq!(vec![1], numbers.skipped_indices().collect::<Vec<_>>());
+/// ```
+#[derive(Debug)]
+pub struct Lenient<T> {
+    items: Vec<T>,
+    skipped: Vec<(usize, Error)>,
+}
+
+impl<T> Lenient<T> {
+    /// items extracted successfully, in document order
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// index in the selected elements and error of each skipped item
+    pub fn skipped(&self) -> &[(usize, Error)] {
+        &self.skipped
+    }
+
+    pub fn skipped_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.skipped.iter().map(|(index, _)| *index)
+    }
+
+    /// whether no item is skipped
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    /// extract an item from each selected element by `extract`
+    fn collect<'a>(
+        select: ElemIter<'_, 'a>,
+        mut extract: impl FnMut(&mut Once<ElementRef<'a>>) -> Result<T>,
+    ) -> Self {
+        let mut lenient = Lenient {
+            items: Vec::new(),
+            skipped: Vec::new(),
+        };
+        for (index, elem) in select.enumerate() {
+            match extract(&mut iter::once(elem)) {
+                Ok(item) => lenient.items.push(item),
+                Err(err) => lenient.skipped.push((index, err.at_index(index, &elem))),
+            }
+        }
+        lenient
+    }
+}
+
+impl<T> Default for Lenient<T> {
+    fn default() -> Self {
+        Lenient {
+            items: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+impl<T> Deref for Lenient<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> IntoIterator for Lenient<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'l, T> IntoIterator for &'l Lenient<T> {
+    type Item = &'l T;
+    type IntoIter = std::slice::Iter<'l, T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+impl<T: FromHtml> FromHtml for Lenient<T> {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_elements(elem)))
+    }
+
+    fn from_elements_report(select: ElemIter) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_elements_report(elem)))
+    }
+
+    fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_elements_with(elem, extraction)
+        }))
+    }
+
+    fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_elements_report_with(elem, extraction)
+        }))
+    }
+}
+
+impl<T: FromText> FromText for Lenient<T> {
+    fn from_inner_text(select: ElemIter) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_inner_text(elem)))
+    }
+
+    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_attr(elem, attr)))
+    }
+
+    fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_inner_text_with(elem, extraction)
+        }))
+    }
+
+    fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_attr_with(elem, attr, extraction)
+        }))
+    }
+}
+
+impl<T: FromSource> FromSource for Lenient<T> {
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_source(elem, source)))
+    }
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_source_with(elem, source, extraction)
+        }))
+    }
+}
+
+impl<'a, T: FromHtmlRef<'a>> FromHtmlRef<'a> for Lenient<T> {
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_elements_ref(elem)))
+    }
+
+    fn from_elements_ref_report(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_elements_ref_report(elem)
+        }))
+    }
+
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_elements_ref_with(elem, extraction)
+        }))
+    }
+
+    fn from_elements_ref_report_with(
+        select: ElemIter<'_, 'a>,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_elements_ref_report_with(elem, extraction)
+        }))
+    }
+}
+
+impl<'a, T: FromTextRef<'a>> FromTextRef<'a> for Lenient<T> {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_inner_text_ref(elem)))
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        Ok(Self::collect(select, |elem| T::from_attr_ref(elem, attr)))
+    }
+
+    fn from_inner_text_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_inner_text_ref_with(elem, extraction)
+        }))
+    }
+
+    fn from_attr_ref_with(
+        select: ElemIter<'_, 'a>,
+        attr: &str,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_attr_ref_with(elem, attr, extraction)
+        }))
+    }
+}
+
+impl<'a, T: FromSourceRef<'a>> FromSourceRef<'a> for Lenient<T> {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_source_ref(elem, source)
+        }))
+    }
+
+    fn from_source_ref_with(
+        select: ElemIter<'_, 'a>,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(Self::collect(select, |elem| {
+            T::from_source_ref_with(elem, source, extraction)
+        }))
+    }
+}
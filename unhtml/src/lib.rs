@@ -5,16 +5,62 @@
 //!
 //!
 
+pub extern crate regex;
 pub extern crate scraper;
+#[cfg(feature = "url")]
+pub extern crate url;
 #[doc(inline)]
-pub use self::err::{Error, Result};
+pub use self::borrow::{FromHtmlRef, FromSourceRef, FromTextRef};
 #[doc(inline)]
-pub use self::traits::{ElemIter, Element, FromHtml, FromText, Select, Text};
+pub use self::charset::decode_html;
+#[doc(inline)]
+pub use self::err::{Context, Error, PathSegment, Result};
+#[doc(inline)]
+pub use self::extraction::Extraction;
+#[doc(inline)]
+pub use self::flag::{BoolVocabulary, FromVocabulary};
+#[doc(inline)]
+pub use self::items::Items;
+#[doc(inline)]
+pub use self::lenient::Lenient;
+#[doc(inline)]
+pub use self::map::{FromPairs, OnDuplicate, PairSelector};
+#[doc(inline)]
+pub use self::raw::{Fragment, InnerHtml, OuterHtml};
+#[doc(inline)]
+pub use self::selector::{LazyRegex, LazySelector, LazyXPath};
+#[doc(inline)]
+pub use self::source::{Source, TextMode};
+#[doc(inline)]
+pub use self::stream::{FromHtmlStream, HtmlStream, ReadStream};
+#[doc(inline)]
+pub use self::table::{FromRow, Row, Table};
+#[doc(inline)]
+pub use self::traits::{ElemIter, Element, FromHtml, FromSource, FromText, Select, Text};
+#[doc(inline)]
+pub use unhtml_xpath::{XPath, XPathError};
 
 #[cfg(feature = "derive")]
-pub use unhtml_derive::{FromHtml, FromText};
+pub use unhtml_derive::{FromHtml, FromRow, FromText};
 
+#[cfg(feature = "url")]
+mod base_url;
+mod borrow;
+mod charset;
+mod container;
+#[cfg(feature = "serde")]
+pub mod de;
 mod err;
+mod extraction;
+mod flag;
+mod items;
+mod lenient;
+mod map;
+mod raw;
+mod selector;
+mod source;
+mod stream;
+mod table;
 #[cfg(test)]
 mod test;
 mod traits;
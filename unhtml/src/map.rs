@@ -0,0 +1,168 @@
+use crate::{ElemIter, Error, Result, Select};
+use scraper::{ElementRef, Selector};
+use std::collections::btree_map::{self, BTreeMap};
+use std::collections::hash_map::{self, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// what to do when a key appears more than once
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum OnDuplicate {
+    /// keep the first value
+    First,
+    /// keep the last value
+    Last,
+    /// fail with `Error::DuplicateKey`
+    Error,
+}
+
+/// maps built from key/value pairs in document order
+pub trait FromPairs<K, V>: Sized {
+    /// insert `pairs` in order, return the index of the duplicate key on `OnDuplicate::Error`
+    fn from_pairs(
+        pairs: impl IntoIterator<Item = (K, V)>,
+        on_duplicate: OnDuplicate,
+    ) -> std::result::Result<Self, usize>;
+}
+
+impl<K, V, S> FromPairs<K, V> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_pairs(
+        pairs: impl IntoIterator<Item = (K, V)>,
+        on_duplicate: OnDuplicate,
+    ) -> std::result::Result<Self, usize> {
+        let mut map = HashMap::default();
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
+            match (map.entry(key), on_duplicate) {
+                (hash_map::Entry::Vacant(entry), _) => {
+                    entry.insert(value);
+                }
+                (hash_map::Entry::Occupied(_), OnDuplicate::First) => (),
+                (hash_map::Entry::Occupied(mut entry), OnDuplicate::Last) => {
+                    entry.insert(value);
+                }
+                (hash_map::Entry::Occupied(_), OnDuplicate::Error) => return Err(index),
+            }
+        }
+        Ok(map)
+    }
+}
+
+impl<K, V> FromPairs<K, V> for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    fn from_pairs(
+        pairs: impl IntoIterator<Item = (K, V)>,
+        on_duplicate: OnDuplicate,
+    ) -> std::result::Result<Self, usize> {
+        let mut map = BTreeMap::new();
+        for (index, (key, value)) in pairs.into_iter().enumerate() {
+            match (map.entry(key), on_duplicate) {
+                (btree_map::Entry::Vacant(entry), _) => {
+                    entry.insert(value);
+                }
+                (btree_map::Entry::Occupied(_), OnDuplicate::First) => (),
+                (btree_map::Entry::Occupied(mut entry), OnDuplicate::Last) => {
+                    entry.insert(value);
+                }
+                (btree_map::Entry::Occupied(_), OnDuplicate::Error) => return Err(index),
+            }
+        }
+        Ok(map)
+    }
+}
+
+/// selects keys and values in each row, they are paired in document order
+///
+/// A row without `key` or `value` selector is the key or the value itself.
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use unhtml::scraper::{Html, Selector};
+/// use unhtml::{FromText, OnDuplicate, PairSelector};
+///
+/// let document = Html::parse_fragment("<dl><dt>Color</dt><dd>Yellow</dd><dt>Size</dt><dd>3</dd></dl>");
+/// let [dl, dt, dd] = ["dl", "dt", "dd"].map(|sel| Selector::parse(sel).unwrap());
+/// let specs: HashMap<String, String> = PairSelector::new(Some(&dt), Some(&dd), OnDuplicate::Error)
+///     .extract(
+///         &mut document.select(&dl),
+///         String::from_inner_text,
+///         String::from_inner_text,
+///     )
+///     .unwrap();
+/// assert_eq!("Yellow", specs["Color"]);
+/// assert_eq!("3", specs["Size"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PairSelector<'s> {
+    key: Option<&'s Selector>,
+    value: Option<&'s Selector>,
+    on_duplicate: OnDuplicate,
+}
+
+impl<'s> PairSelector<'s> {
+    pub fn new(
+        key: Option<&'s Selector>,
+        value: Option<&'s Selector>,
+        on_duplicate: OnDuplicate,
+    ) -> Self {
+        Self {
+            key,
+            value,
+            on_duplicate,
+        }
+    }
+
+    /// extract a map from `rows`, keys and values are extracted from the selected elements
+    /// by `key` and `value`
+    ///
+    /// Errors are tagged with the index of the row, and the key or value element failing
+    /// or the row if its keys and values don't match in count.
+    pub fn extract<'a, M, K, V>(
+        &self,
+        rows: ElemIter<'_, 'a>,
+        mut key: impl FnMut(ElemIter<'_, 'a>) -> Result<K>,
+        mut value: impl FnMut(ElemIter<'_, 'a>) -> Result<V>,
+    ) -> Result<M>
+    where
+        M: FromPairs<K, V>,
+    {
+        let mut pairs = Vec::new();
+        let mut keys_at = Vec::new();
+        for (index, row) in rows.enumerate() {
+            let keys = select(row, self.key);
+            let values = select(row, self.value);
+            if keys.len() != values.len() {
+                return Err(Error::CountMismatch {
+                    expected: keys.len(),
+                    found: values.len(),
+                }
+                .at_index(index, &row));
+            }
+            for (key_elem, value_elem) in keys.into_iter().zip(values) {
+                let pair = (
+                    key(&mut Some(key_elem).into_iter())
+                        .map_err(|err| err.at_index(index, &key_elem))?,
+                    value(&mut Some(value_elem).into_iter())
+                        .map_err(|err| err.at_index(index, &value_elem))?,
+                );
+                pairs.push(pair);
+                keys_at.push((index, key_elem));
+            }
+        }
+        M::from_pairs(pairs, self.on_duplicate).map_err(|pair| {
+            let (index, key_elem) = keys_at[pair];
+            Error::DuplicateKey.at_index(index, &key_elem)
+        })
+    }
+}
+
+fn select<'a>(row: ElementRef<'a>, selector: Option<&Selector>) -> Vec<ElementRef<'a>> {
+    match selector {
+        Some(selector) => Some(row).into_iter().select_elements(selector).collect(),
+        None => vec![row],
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{ElemIter, FromHtml, FromHtmlRef, Result};
+use scraper::{ElementRef, Html};
+use std::fmt::{self, Display, Formatter};
+use std::iter;
+use std::ops::Deref;
+
+/// html of the whole selected element, including its own tag
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct OuterHtml(pub String);
+
+/// html of the children of the selected element
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct InnerHtml(pub String);
+
+/// the whole selected element parsed again as a standalone fragment,
+/// it can be extracted into other types later
+///
+/// ```rust
+/// use unhtml::{scraper::Selector, Fragment, FromHtml, Element};
+///
+/// let html = r#"<div><a href="https://github.com">Github</a></div>"#;
+/// let selector = Selector::parse("div").unwrap();
+/// let document = unhtml::scraper::Html::parse_fragment(html);
+/// let fragment: Fragment = document.select(&selector).element().unwrap();
+/// assert_eq!(html, fragment.to_string());
+/// let source: String = fragment.extract().unwrap();
+/// assert_eq!(format!("<html>{}</html>", html), source);
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Fragment(Html);
+
+impl Fragment {
+    /// the parsed fragment
+    pub fn html(&self) -> &Html {
+        &self.0
+    }
+
+    /// extract `T` from this fragment, the top element is `html` like `FromHtml::from_html`
+    pub fn extract<T: FromHtml>(&self) -> Result<T> {
+        T::from_elements(&mut iter::once(self.0.root_element()))
+    }
+
+    /// extract `T` borrowing from this fragment
+    pub fn extract_ref<'a, T: FromHtmlRef<'a>>(&'a self) -> Result<T> {
+        T::from_elements_ref(&mut iter::once(self.0.root_element()))
+    }
+}
+
+impl Deref for OuterHtml {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for InnerHtml {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for OuterHtml {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for InnerHtml {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for Fragment {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0.root_element().inner_html())
+    }
+}
+
+/// html of the whole first element, like `OuterHtml`
+impl FromHtml for String {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Ok(first.html())
+    }
+}
+
+impl FromHtml for OuterHtml {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Ok(OuterHtml(first.html()))
+    }
+}
+
+impl FromHtml for InnerHtml {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Ok(InnerHtml(first.inner_html()))
+    }
+}
+
+impl FromHtml for Fragment {
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        Ok(Fragment(Html::parse_fragment(&first.html())))
+    }
+}
+
+impl<'a> FromHtmlRef<'a> for ElementRef<'a> {
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Ok(select.next().ok_or(())?)
+    }
+}
+
+macro_rules! from_html_ref {
+    ($($typ:ty),*) => {
+        $(
+            impl<'a> FromHtmlRef<'a> for $typ {
+                fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    Self::from_elements(select)
+                }
+            }
+        )*
+    };
+}
+
+from_html_ref!(String, OuterHtml, InnerHtml, Fragment);
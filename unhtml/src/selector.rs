@@ -0,0 +1,105 @@
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use scraper::Selector;
+use std::ops::Deref;
+use unhtml_xpath::XPath;
+
+/// a css selector parsed on first use, so it can be kept in a `static`
+///
+/// ```rust
+/// use unhtml::{scraper::Html, LazySelector};
+///
+/// static LINK: LazySelector = LazySelector::new("a");
+///
+/// let html = Html::parse_fragment(r#"<a href="https://github.com">Github</a>"#);
+/// assert_eq!(1, html.select(&LINK).count());
+/// ```
+pub struct LazySelector {
+    selector: &'static str,
+    cell: OnceCell<Selector>,
+}
+
+impl LazySelector {
+    pub const fn new(selector: &'static str) -> Self {
+        Self {
+            selector,
+            cell: OnceCell::new(),
+        }
+    }
+}
+
+impl Deref for LazySelector {
+    type Target = Selector;
+
+    /// panic if the selector is invalid
+    fn deref(&self) -> &Selector {
+        self.cell.get_or_init(|| {
+            Selector::parse(self.selector)
+                .unwrap_or_else(|err| panic!("invalid css selector `{}`: {:?}", self.selector, err))
+        })
+    }
+}
+
+/// a regex compiled on first use, so it can be kept in a `static`
+pub struct LazyRegex {
+    pattern: &'static str,
+    cell: OnceCell<Regex>,
+}
+
+impl LazyRegex {
+    pub const fn new(pattern: &'static str) -> Self {
+        Self {
+            pattern,
+            cell: OnceCell::new(),
+        }
+    }
+}
+
+impl Deref for LazyRegex {
+    type Target = Regex;
+
+    /// panic if the pattern is invalid
+    fn deref(&self) -> &Regex {
+        self.cell.get_or_init(|| {
+            Regex::new(self.pattern)
+                .unwrap_or_else(|err| panic!("invalid regex `{}`: {}", self.pattern, err))
+        })
+    }
+}
+
+/// an xpath parsed on first use, so it can be kept in a `static`
+///
+/// ```rust
+/// use unhtml::{scraper::Html, LazyXPath, Select};
+///
+/// static PRICE: LazyXPath = LazyXPath::new("//dt[text()='Price']/following-sibling::dd[1]");
+///
+/// let html = Html::parse_document("<dl><dt>Color</dt><dd>Yellow</dd><dt>Price</dt><dd>12</dd></dl>");
+/// let price = Some(html.root_element()).into_iter().select_xpath(&PRICE).next().unwrap();
+/// assert_eq!("12", price.inner_html());
+/// ```
+pub struct LazyXPath {
+    xpath: &'static str,
+    cell: OnceCell<XPath>,
+}
+
+impl LazyXPath {
+    pub const fn new(xpath: &'static str) -> Self {
+        Self {
+            xpath,
+            cell: OnceCell::new(),
+        }
+    }
+}
+
+impl Deref for LazyXPath {
+    type Target = XPath;
+
+    /// panic if the xpath is invalid
+    fn deref(&self) -> &XPath {
+        self.cell.get_or_init(|| {
+            XPath::parse(self.xpath)
+                .unwrap_or_else(|err| panic!("invalid xpath `{}`: {}", self.xpath, err))
+        })
+    }
+}
@@ -0,0 +1,290 @@
+use crate::{ElemIter, Error, Result};
+use regex::Regex;
+use scraper::{ElementRef, Node};
+use std::fmt::Display;
+
+/// how text nodes of an element are joined into its inner text
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TextMode {
+    /// all text nodes as they are
+    Raw,
+    /// trim each text node and concatenate them, the default of `text`
+    Trim,
+    /// trim each text node and join non-empty ones with a space
+    Join,
+    /// collapse every run of whitespace, including `&nbsp;`, into a space
+    Collapse,
+    /// like `Collapse`, but break lines at `<br>` and around block elements,
+    /// and separate table cells like `innerText`
+    Block,
+}
+
+/// elements starting a new line in `TextMode::Block`
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// table cells separated by a space in `TextMode::Block`
+const CELL_ELEMENTS: &[&str] = &["td", "th"];
+
+impl TextMode {
+    /// inner text of `element` in this mode
+    pub fn text(self, element: ElementRef) -> String {
+        match self {
+            TextMode::Block => block_text(element, true),
+            _ => self.join(element.text()),
+        }
+    }
+
+    /// text of the direct child text nodes of `element` in this mode,
+    /// text in child elements is skipped
+    pub fn own_text(self, element: ElementRef) -> String {
+        match self {
+            TextMode::Block => block_text(element, false),
+            _ => self.join(element.children().filter_map(|child| match child.value() {
+                Node::Text(segment) => Some(&**segment),
+                _ => None,
+            })),
+        }
+    }
+
+    fn join<'t>(self, segments: impl Iterator<Item = &'t str>) -> String {
+        match self {
+            TextMode::Raw => segments.collect(),
+            TextMode::Trim => segments.map(str::trim).collect(),
+            TextMode::Join => segments
+                .map(str::trim)
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+            TextMode::Collapse | TextMode::Block => collapse(&segments.collect::<String>()),
+        }
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// text of `element` with line breaks, `deep` includes text in child elements
+fn block_text(element: ElementRef, deep: bool) -> String {
+    let mut text = String::new();
+    push_block_text(element, deep, &mut text);
+    let lines: Vec<_> = text.lines().map(collapse).collect();
+    lines.join("\n").trim_matches('\n').to_owned()
+}
+
+fn push_block_text(element: ElementRef, deep: bool, text: &mut String) {
+    let name = element.value().name();
+    if name == "br" {
+        text.push('\n');
+        return;
+    }
+    let block = BLOCK_ELEMENTS.contains(&name);
+    if block {
+        break_line(text);
+    } else if CELL_ELEMENTS.contains(&name) {
+        text.push(' ');
+    }
+    for child in element.children() {
+        match child.value() {
+            Node::Text(segment) => {
+                text.extend(
+                    segment
+                        .chars()
+                        .map(|c| if c.is_whitespace() { ' ' } else { c }),
+                )
+            }
+            Node::Element(_) if deep => {
+                push_block_text(ElementRef::wrap(child).unwrap(), deep, text)
+            }
+            Node::Element(child) if child.name() == "br" => text.push('\n'),
+            Node::Element(child) if BLOCK_ELEMENTS.contains(&child.name()) => break_line(text),
+            Node::Element(child) if CELL_ELEMENTS.contains(&child.name()) => text.push(' '),
+            _ => (),
+        }
+    }
+    if block {
+        break_line(text);
+    }
+}
+
+/// start a new line unless the current one is blank
+fn break_line(text: &mut String) {
+    let line_start = text.rfind('\n').map_or(0, |index| index + 1);
+    if text[line_start..].trim().is_empty() {
+        text.truncate(line_start);
+    } else {
+        text.push('\n');
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum SourceKind<'s> {
+    InnerText(TextMode),
+    OwnText(TextMode),
+    InnerHtml,
+    OuterHtml,
+    Tag,
+    Attr(&'s str),
+}
+
+/// where the text of a field is taken from, passed to `FromSource::from_source`
+///
+/// ```rust
+/// use unhtml::regex::Regex;
+/// use unhtml::scraper::{Html, Selector};
+/// use unhtml::{FromSource, Source, TextMode};
+///
+/// let html = Html::parse_fragment("<p>Price: 12.50 EUR</p>");
+/// let regex = Regex::new(r"([\d.]+) EUR").unwrap();
+/// let source = Source::inner_text(TextMode::Trim).with_regex(&regex, None);
+/// let price = f64::from_source(&mut html.select(&Selector::parse("p").unwrap()), &source);
+/// assert_eq!(12.5, price.unwrap());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Source<'s> {
+    kind: SourceKind<'s>,
+    regex: Option<(&'s Regex, Option<&'s str>)>,
+}
+
+impl<'s> Source<'s> {
+    /// inner text joined in `mode`
+    pub const fn inner_text(mode: TextMode) -> Self {
+        Self {
+            kind: SourceKind::InnerText(mode),
+            regex: None,
+        }
+    }
+
+    /// direct child text joined in `mode`, like `own_text` of a derived field
+    pub const fn own_text(mode: TextMode) -> Self {
+        Self {
+            kind: SourceKind::OwnText(mode),
+            regex: None,
+        }
+    }
+
+    /// html of the children
+    pub const fn inner_html() -> Self {
+        Self {
+            kind: SourceKind::InnerHtml,
+            regex: None,
+        }
+    }
+
+    /// html of the whole element
+    pub const fn outer_html() -> Self {
+        Self {
+            kind: SourceKind::OuterHtml,
+            regex: None,
+        }
+    }
+
+    /// name of the element, like `div`
+    pub const fn tag() -> Self {
+        Self {
+            kind: SourceKind::Tag,
+            regex: None,
+        }
+    }
+
+    /// value of the attribute `attr`, trimmed
+    pub const fn attr(attr: &'s str) -> Self {
+        Self {
+            kind: SourceKind::Attr(attr),
+            regex: None,
+        }
+    }
+
+    /// take the named `group`, or the first capture group, or the whole match of `regex` in the text
+    pub const fn with_regex(self, regex: &'s Regex, group: Option<&'s str>) -> Self {
+        Self {
+            kind: self.kind,
+            regex: Some((regex, group)),
+        }
+    }
+
+    /// take the text from `element`
+    pub fn text(&self, element: ElementRef) -> Result<String> {
+        let text = match self.kind {
+            SourceKind::InnerText(mode) => mode.text(element),
+            SourceKind::OwnText(mode) => mode.own_text(element),
+            SourceKind::InnerHtml => element.inner_html(),
+            SourceKind::OuterHtml => element.html(),
+            SourceKind::Tag => element.value().name().to_owned(),
+            SourceKind::Attr(attr) => match element.value().attr(attr) {
+                Some(value) => value.trim().to_owned(),
+                None => return Err((attr.to_owned(), element.html()).into()),
+            },
+        };
+        match self.regex {
+            Some((regex, group)) => {
+                capture(regex, group, &text)
+                    .map(ToOwned::to_owned)
+                    .ok_or(Error::TextParseError {
+                        err: format!("no match of regex `{}`", regex),
+                        type_name: "regex".to_owned(),
+                        text,
+                    })
+            }
+            None => Ok(text),
+        }
+    }
+
+    /// parse the text taken from the first element by `parse`, like `parse_with` of a derived field
+    pub fn parse_with<T, E: Display>(
+        &self,
+        select: ElemIter,
+        parse: impl FnOnce(&str) -> std::result::Result<T, E>,
+    ) -> Result<T> {
+        let first = select.next().ok_or(())?;
+        let text = self.text(first)?;
+        parse(&text).map_err(|err| Error::TextParseError {
+            err: err.to_string(),
+            type_name: std::any::type_name::<T>().to_owned(),
+            text,
+        })
+    }
+}
+
+fn capture<'t>(regex: &Regex, group: Option<&str>, text: &'t str) -> Option<&'t str> {
+    let captures = regex.captures(text)?;
+    let matched = match group {
+        Some(name) => captures.name(name),
+        None => captures.get(1).or_else(|| captures.get(0)),
+    };
+    matched.map(|matched| matched.as_str())
+}
@@ -0,0 +1,402 @@
+use crate::{ElemIter, Error, Result};
+use ego_tree::NodeId;
+use html5ever::tokenizer::states::RawKind;
+use html5ever::tokenizer::{
+    BufferQueue, Tag, TagKind, Token, TokenSink, TokenSinkResult, Tokenizer, TokenizerOpts,
+};
+use html5ever::{LocalName, Namespace, QualName};
+use scraper::node::{Comment, Element, Text};
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::VecDeque;
+use std::io::{self, Read};
+use std::iter;
+
+pub(crate) const HTML_NAMESPACE: &str = "http://www.w3.org/1999/xhtml";
+const READ_SIZE: usize = 64 * 1024;
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// types extracted from html one item at a time, without building the whole document
+///
+/// It's implemented by `#[derive(FromHtml)]` with `#[html(selector = "...", stream)]`.
+/// Each element matching `item_selector` is built into a small tree when it's closed and extracted by `from_item`,
+/// so the selector can only look at the element and its ancestors:
+/// tags, ids, classes, attributes and ` ` or `>` combinators, but no `+`, `~` or pseudo-classes.
+///
+/// ```rust,ignore
+/// use unhtml::{FromHtml, FromHtmlStream};
+///
+/// #[derive(FromHtml)]
+/// #[html(selector = "#logs > li", stream)]
+/// struct Log {
+///     #[html(selector = ".level", text)]
+///     level: String,
+/// }
+///
+/// let file = std::fs::File::open("logs.html").unwrap();
+/// for log in Log::stream_reader(file) {
+///     println!("{}", log.unwrap().level);
+/// }
+/// ```
+pub trait FromHtmlStream: Sized {
+    fn item_selector() -> &'static Selector;
+
+    /// extract an item from its element, without selecting by `item_selector` again
+    fn from_item(select: ElemIter) -> Result<Self>;
+
+    /// items of html fed in chunks
+    fn stream() -> HtmlStream<Self> {
+        HtmlStream::new()
+    }
+
+    /// items of utf-8 html read from `reader`
+    fn stream_reader<R: Read>(reader: R) -> ReadStream<Self, R> {
+        ReadStream {
+            reader: Some(reader),
+            stream: HtmlStream::new(),
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// items extracted from html fed in chunks, an item is ready once its element is closed
+///
+/// The elements are built by a simplified html tree construction:
+/// an end tag closes the nearest open element with the same name,
+/// and only `li`, `dt`, `dd`, `option`, `tr`, `td`, `th` and `p` are closed by a following start tag.
+pub struct HtmlStream<T: FromHtmlStream> {
+    tokenizer: Tokenizer<Sink<T>>,
+    queue: BufferQueue,
+}
+
+impl<T: FromHtmlStream> HtmlStream<T> {
+    pub fn new() -> Self {
+        Self {
+            tokenizer: Tokenizer::new(Sink::new(), TokenizerOpts::default()),
+            queue: BufferQueue::new(),
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &str) {
+        self.queue.push_back(chunk.into());
+        let _ = self.tokenizer.feed(&mut self.queue);
+    }
+
+    /// close all open elements at the end of html
+    pub fn finish(&mut self) {
+        self.tokenizer.end();
+    }
+}
+
+impl<T: FromHtmlStream> Default for HtmlStream<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// items ready so far
+impl<T: FromHtmlStream> Iterator for HtmlStream<T> {
+    type Item = Result<T>;
+    fn next(&mut self) -> Option<Result<T>> {
+        self.tokenizer.sink.items.pop_front()
+    }
+}
+
+/// items extracted from html read from a reader
+pub struct ReadStream<T: FromHtmlStream, R> {
+    reader: Option<R>,
+    stream: HtmlStream<T>,
+    /// bytes of an incomplete utf-8 char at the end of the last read
+    pending: Vec<u8>,
+}
+
+impl<T: FromHtmlStream, R: Read> ReadStream<T, R> {
+    fn read(&mut self) -> io::Result<()> {
+        let reader = match self.reader.as_mut() {
+            Some(reader) => reader,
+            None => return Ok(()),
+        };
+        let start = self.pending.len();
+        self.pending.resize(start + READ_SIZE, 0);
+        let read = match reader.read(&mut self.pending[start..]) {
+            Ok(read) => read,
+            Err(err) => {
+                self.pending.truncate(start);
+                return Err(err);
+            }
+        };
+        self.pending.truncate(start + read);
+        // an incomplete char is only invalid at the end of html
+        let (valid, invalid) = match std::str::from_utf8(&self.pending) {
+            Ok(text) => (text.len(), false),
+            Err(err) => (err.valid_up_to(), read == 0 || err.error_len().is_some()),
+        };
+        // the prefix is checked by `from_utf8`
+        self.stream
+            .feed(std::str::from_utf8(&self.pending[..valid]).unwrap_or_default());
+        self.pending.drain(..valid);
+        if read == 0 || invalid {
+            // items open so far are closed and still yielded after the error
+            self.reader = None;
+            self.stream.finish();
+        }
+        if invalid {
+            return Err(invalid_utf8());
+        }
+        Ok(())
+    }
+}
+
+impl<T: FromHtmlStream, R: Read> Iterator for ReadStream<T, R> {
+    type Item = Result<T>;
+    fn next(&mut self) -> Option<Result<T>> {
+        loop {
+            if let Some(item) = self.stream.next() {
+                return Some(item);
+            }
+            self.reader.as_ref()?;
+            if let Err(err) = self.read() {
+                return Some(Err(Error::Io(err)));
+            }
+        }
+    }
+}
+
+fn invalid_utf8() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "stream did not contain valid UTF-8",
+    )
+}
+
+/// an open element
+enum Open {
+    /// an ancestor of items in the skeleton, kept to match the item selector
+    Ancestor(NodeId),
+    /// an element in the item being built
+    Node(NodeId),
+}
+
+struct Sink<T> {
+    open: Vec<Open>,
+    /// a chain of the open ancestors, the node at each depth is reused by the next element there
+    skeleton: Html,
+    chain: Vec<NodeId>,
+    /// the item being built and its depth in `open`
+    item: Option<(Html, usize)>,
+    items: VecDeque<Result<T>>,
+}
+
+impl<T: FromHtmlStream> Sink<T> {
+    fn new() -> Self {
+        Self {
+            open: Vec::new(),
+            skeleton: Html::new_fragment(),
+            chain: Vec::new(),
+            item: None,
+            items: VecDeque::new(),
+        }
+    }
+
+    fn name<'s>(&'s self, open: &'s Open) -> &'s str {
+        let (html, id) = match (open, &self.item) {
+            (Open::Ancestor(id), _) => (&self.skeleton, id),
+            (Open::Node(id), Some((html, _))) => (html, id),
+            (Open::Node(_), None) => return "",
+        };
+        html.tree
+            .get(*id)
+            .and_then(|node| node.value().as_element())
+            .map_or("", |element| &element.name.local)
+    }
+
+    /// put the new element under the open ancestors in the skeleton,
+    /// ancestors are a prefix of `open` as no item is being built
+    fn place(&mut self, element: Node) -> Option<NodeId> {
+        let depth = self.open.len();
+        if let Some(&id) = self.chain.get(depth) {
+            *self.skeleton.tree.get_mut(id)?.value() = element;
+            return Some(id);
+        }
+        let parent = self
+            .chain
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.skeleton.tree.root().id());
+        let id = self.skeleton.tree.get_mut(parent)?.append(element).id();
+        self.chain.push(id);
+        Some(id)
+    }
+
+    /// whether the element placed at `id` matches the item selector
+    fn is_item(&self, id: NodeId) -> bool {
+        self.skeleton
+            .tree
+            .get(id)
+            .and_then(ElementRef::wrap)
+            .is_some_and(|element| T::item_selector().matches(&element))
+    }
+
+    /// append `node` to the current element of the item
+    fn append(&mut self, node: Node) -> Option<NodeId> {
+        let (html, _) = self.item.as_mut()?;
+        let parent = match self.open.last() {
+            Some(Open::Node(id)) => *id,
+            _ => html.tree.root().id(),
+        };
+        let mut parent = html.tree.get_mut(parent)?;
+        if let (Node::Text(text), Some(mut last)) = (&node, parent.last_child()) {
+            if let Node::Text(last) = last.value() {
+                last.text.push_tendril(&text.text);
+                return None;
+            }
+        }
+        Some(parent.append(node).id())
+    }
+
+    fn start(&mut self, tag: Tag) {
+        while let Some(open) = self.open.last() {
+            if !closes(&tag.name, self.name(open)) {
+                break;
+            }
+            self.pop();
+        }
+        let void = tag.self_closing || VOID_ELEMENTS.contains(&&*tag.name);
+        let name = QualName::new(None, Namespace::from(HTML_NAMESPACE), tag.name);
+        let mut element = Node::Element(Element::new(name, tag.attrs));
+        if self.item.is_none() {
+            let id = match self.place(element) {
+                Some(id) => id,
+                None => return,
+            };
+            if !self.is_item(id) {
+                if !void {
+                    self.open.push(Open::Ancestor(id));
+                }
+                return;
+            }
+            // the item element moves out of the skeleton, its node is reused by the next element
+            element = match self.skeleton.tree.get_mut(id) {
+                Some(mut node) => std::mem::replace(node.value(), Node::Fragment),
+                None => return,
+            };
+            self.item = Some((Html::new_fragment(), self.open.len()));
+        }
+        if let Some(id) = self.append(element) {
+            self.open.push(Open::Node(id));
+            if void {
+                self.pop();
+            }
+        }
+    }
+
+    fn end(&mut self, name: &LocalName) {
+        if let Some(index) = self
+            .open
+            .iter()
+            .rposition(|open| self.name(open) == &**name)
+        {
+            while self.open.len() > index {
+                self.pop();
+            }
+        }
+    }
+
+    /// close the current element, the item is extracted once its element is closed
+    fn pop(&mut self) {
+        self.open.pop();
+        let finished = matches!(&self.item, Some((_, depth)) if *depth == self.open.len());
+        if !finished {
+            return;
+        }
+        if let Some((html, _)) = self.item.take() {
+            let item = html
+                .tree
+                .root()
+                .first_child()
+                .and_then(ElementRef::wrap)
+                .ok_or(Error::SourceNotFound)
+                .and_then(|element| T::from_item(&mut iter::once(element)));
+            self.items.push_back(item);
+        }
+    }
+}
+
+impl<T: FromHtmlStream> TokenSink for Sink<T> {
+    type Handle = ();
+
+    fn process_token(&mut self, token: Token, _line_number: u64) -> TokenSinkResult<()> {
+        match token {
+            Token::TagToken(tag) if tag.kind == TagKind::StartTag => {
+                let raw = match &*tag.name {
+                    "title" | "textarea" => Some(RawKind::Rcdata),
+                    "style" | "xmp" | "iframe" | "noembed" | "noframes" => Some(RawKind::Rawtext),
+                    "script" => Some(RawKind::ScriptData),
+                    _ => None,
+                };
+                let self_closing = tag.self_closing;
+                self.start(tag);
+                match raw {
+                    Some(raw) if !self_closing => return TokenSinkResult::RawData(raw),
+                    _ => (),
+                }
+            }
+            Token::TagToken(tag) => self.end(&tag.name),
+            Token::CharacterTokens(text) if self.item.is_some() => {
+                self.append(Node::Text(Text { text }));
+            }
+            Token::CommentToken(comment) if self.item.is_some() => {
+                self.append(Node::Comment(Comment { comment }));
+            }
+            Token::EOFToken => {
+                while !self.open.is_empty() {
+                    self.pop();
+                }
+            }
+            _ => (),
+        }
+        TokenSinkResult::Continue
+    }
+}
+
+/// whether a start tag `new` closes the open element `open`, a small part of html tree construction
+fn closes(new: &str, open: &str) -> bool {
+    match open {
+        "li" => new == "li",
+        "dt" | "dd" => matches!(new, "dt" | "dd"),
+        "option" => matches!(new, "option" | "optgroup"),
+        "tr" => new == "tr",
+        "td" | "th" => matches!(new, "td" | "th" | "tr"),
+        "p" => matches!(
+            new,
+            "address"
+                | "article"
+                | "aside"
+                | "blockquote"
+                | "div"
+                | "dl"
+                | "fieldset"
+                | "footer"
+                | "form"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "header"
+                | "hr"
+                | "main"
+                | "nav"
+                | "ol"
+                | "p"
+                | "pre"
+                | "section"
+                | "table"
+                | "ul"
+        ),
+        _ => false,
+    }
+}
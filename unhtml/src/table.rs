@@ -0,0 +1,255 @@
+use crate::{ElemIter, Extraction, FromHtml, FromHtmlRef, LazySelector, Result};
+use scraper::ElementRef;
+use std::ops::Deref;
+
+/// a row of a `Table`, cells are addressed by column header
+pub struct Row<'r, 'a> {
+    element: ElementRef<'a>,
+    headers: &'r [String],
+    cells: Vec<Option<ElementRef<'a>>>,
+}
+
+impl<'r, 'a> Row<'r, 'a> {
+    /// the `tr` element
+    pub fn element(&self) -> ElementRef<'a> {
+        self.element
+    }
+
+    /// headers of all columns
+    pub fn headers(&self) -> &'r [String] {
+        self.headers
+    }
+
+    /// cell in the first column with this header,
+    /// a cell spanning several columns or rows appears in each of them
+    pub fn cell(&self, header: &str) -> Option<ElementRef<'a>> {
+        let header = normalize(header.split_whitespace());
+        let index = self.headers.iter().position(|name| *name == header)?;
+        self.cell_at(index)
+    }
+
+    /// cell in the column at `index`
+    pub fn cell_at(&self, index: usize) -> Option<ElementRef<'a>> {
+        self.cells.get(index).copied().flatten()
+    }
+}
+
+/// parse a table row, implemented by `#[derive(FromRow)]`
+pub trait FromRow<'a>: Sized {
+    fn from_row(row: &Row<'_, 'a>) -> Result<Self>;
+
+    /// like `FromHtml::from_elements_with`
+    fn from_row_with(row: &Row<'_, 'a>, _extraction: &Extraction) -> Result<Self> {
+        Self::from_row(row)
+    }
+}
+
+static TABLE: LazySelector = LazySelector::new("table");
+
+/// rows of the first selected `table`, or the first `table` in the selected element,
+/// mapped by column header
+///
+/// Header rows are the rows in `thead`, or the leading rows made of only `th` without `thead`.
+/// The header of a column is the text of its header cells from top to bottom, joined by a space.
+/// Cells with `colspan` or `rowspan` are repeated in every column and row they span.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Table<T> {
+    headers: Vec<String>,
+    rows: Vec<T>,
+}
+
+impl<T> Table<T> {
+    pub fn headers(&self) -> &[String] {
+        &self.headers
+    }
+
+    pub fn into_rows(self) -> Vec<T> {
+        self.rows
+    }
+}
+
+impl<T> Deref for Table<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.rows
+    }
+}
+
+impl<T> IntoIterator for Table<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<T> FromHtml for Table<T>
+where
+    T: for<'a> FromRow<'a>,
+{
+    fn from_elements(select: ElemIter) -> Result<Self> {
+        extract_table(select, &Extraction::default())
+    }
+
+    fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        extract_table(select, extraction)
+    }
+}
+
+impl<'a, T> FromHtmlRef<'a> for Table<T>
+where
+    T: FromRow<'a>,
+{
+    fn from_elements_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        extract_table(select, &Extraction::default())
+    }
+
+    fn from_elements_ref_with(select: ElemIter<'_, 'a>, extraction: &Extraction) -> Result<Self> {
+        extract_table(select, extraction)
+    }
+}
+
+fn extract_table<'a, T: FromRow<'a>>(
+    select: ElemIter<'_, 'a>,
+    extraction: &Extraction,
+) -> Result<Table<T>> {
+    let first = select.next().ok_or(())?;
+    let table = if first.value().name() == "table" {
+        first
+    } else {
+        first.select(&TABLE).next().ok_or(())?
+    };
+    let (header_rows, body_rows) = split_rows(table);
+    let mut grid = expand_spans(header_rows.iter().chain(&body_rows));
+    let body_grid = grid.split_off(header_rows.len());
+    let columns = grid
+        .iter()
+        .chain(&body_grid)
+        .map(Vec::len)
+        .max()
+        .unwrap_or(0);
+    let headers: Vec<_> = (0..columns)
+        .map(|index| {
+            let mut cells: Vec<ElementRef> = grid
+                .iter()
+                .filter_map(|row| row.get(index).copied().flatten())
+                .collect();
+            cells.dedup();
+            normalize(cells.iter().flat_map(|cell| cell.text()))
+        })
+        .collect();
+    let mut rows = Vec::with_capacity(body_rows.len());
+    for (index, (element, cells)) in body_rows.into_iter().zip(body_grid).enumerate() {
+        let row = Row {
+            element,
+            headers: &headers,
+            cells,
+        };
+        rows.push(T::from_row_with(&row, extraction).map_err(|err| err.at_index(index, &element))?);
+    }
+    Ok(Table { headers, rows })
+}
+
+/// `tr` of this table, but not of nested tables, split into header rows and body rows
+fn split_rows(table: ElementRef) -> (Vec<ElementRef>, Vec<ElementRef>) {
+    let mut header_rows = Vec::new();
+    let mut body_rows = Vec::new();
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "thead" => header_rows.extend(child_elements(child, "tr")),
+            "tbody" | "tfoot" => body_rows.extend(child_elements(child, "tr")),
+            "tr" => body_rows.push(child),
+            _ => (),
+        }
+    }
+    if header_rows.is_empty() {
+        let count = body_rows
+            .iter()
+            .take_while(|row| {
+                let mut cells = cells(**row).peekable();
+                cells.peek().is_some() && cells.all(|cell| cell.value().name() == "th")
+            })
+            .count();
+        header_rows = body_rows.drain(..count).collect();
+    }
+    (header_rows, body_rows)
+}
+
+fn child_elements<'a>(
+    parent: ElementRef<'a>,
+    name: &'static str,
+) -> impl Iterator<Item = ElementRef<'a>> {
+    parent
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(move |child| child.value().name() == name)
+}
+
+fn cells(row: ElementRef) -> impl Iterator<Item = ElementRef> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+}
+
+/// cells of each row by column, with `colspan` and `rowspan` applied
+fn expand_spans<'r, 'a: 'r>(
+    rows: impl Iterator<Item = &'r ElementRef<'a>>,
+) -> Vec<Vec<Option<ElementRef<'a>>>> {
+    // cells spanning into following rows and how many rows are left
+    let mut pending: Vec<Option<(usize, ElementRef)>> = Vec::new();
+    let mut grid = Vec::new();
+    for row in rows {
+        let mut columns = Vec::new();
+        let mut own_cells = cells(*row);
+        loop {
+            let index = columns.len();
+            if let Some(Some((rest, cell))) = pending.get_mut(index) {
+                columns.push(Some(*cell));
+                *rest -= 1;
+                if *rest == 0 {
+                    pending[index] = None;
+                }
+                continue;
+            }
+            let cell = match own_cells.next() {
+                Some(cell) => cell,
+                None if pending.iter().skip(index).any(Option::is_some) => {
+                    columns.push(None);
+                    continue;
+                }
+                None => break,
+            };
+            let colspan = span(cell, "colspan");
+            let rowspan = span(cell, "rowspan");
+            for _ in 0..colspan {
+                let index = columns.len();
+                columns.push(Some(cell));
+                if rowspan > 1 {
+                    if pending.len() <= index {
+                        pending.resize(index + 1, None);
+                    }
+                    pending[index] = Some((rowspan - 1, cell));
+                }
+            }
+        }
+        grid.push(columns);
+    }
+    grid
+}
+
+/// max value of `colspan` and `rowspan`, like browsers
+const SPAN_LIMIT: usize = 1000;
+
+fn span(cell: ElementRef, attr: &str) -> usize {
+    cell.value()
+        .attr(attr)
+        .and_then(|value| value.trim().parse().ok())
+        .map_or(1, |span: usize| span.clamp(1, SPAN_LIMIT))
+}
+
+/// words joined by a space
+fn normalize<'t>(text: impl Iterator<Item = &'t str>) -> String {
+    text.flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,49 @@
+use crate::decode_html;
+use encoding_rs::{GBK, SHIFT_JIS, WINDOWS_1252};
+
+#[test]
+fn test_decode_meta_charset() {
+    let (bytes, _, _) = SHIFT_JIS.encode("<meta charset=\"Shift_JIS\"><p>日本語</p>");
+    assert_eq!(
+        "<meta charset=\"Shift_JIS\"><p>日本語</p>",
+        decode_html(&bytes, None)
+    );
+    let html = r#"<!-- <meta charset="utf-8"> --><META http-equiv=Content-Type content='text/html; charset="gbk"'><p>中文</p>"#;
+    let (bytes, _, _) = GBK.encode(html);
+    assert_eq!(html, decode_html(&bytes, None));
+}
+
+#[test]
+fn test_decode_hint() {
+    let (bytes, _, _) = GBK.encode("<meta charset=\"Shift_JIS\"><p>中文</p>");
+    assert!(decode_html(&bytes, Some("text/html; charset=GBK")).contains("中文"));
+    assert!(decode_html(&bytes, Some("gb2312")).contains("中文"));
+    assert!(!decode_html(&bytes, None).contains("中文"));
+    // an unknown hint is ignored
+    assert!(!decode_html(&bytes, Some("text/html")).contains("中文"));
+}
+
+#[test]
+fn test_decode_bom() {
+    let mut bytes = vec![0xef, 0xbb, 0xbf];
+    bytes.extend_from_slice("<meta charset=\"gbk\"><p>中文</p>".as_bytes());
+    assert_eq!(
+        "<meta charset=\"gbk\"><p>中文</p>",
+        decode_html(&bytes, Some("Shift_JIS"))
+    );
+    let mut bytes = vec![0xff, 0xfe];
+    for unit in "<p>ü</p>".encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+    assert_eq!("<p>ü</p>", decode_html(&bytes, None));
+    // declared utf-16 without bom is utf-8
+    let html = "<meta charset=\"utf-16\"><p>ü</p>";
+    assert_eq!(html, decode_html(html.as_bytes(), None));
+}
+
+#[test]
+fn test_decode_fallback() {
+    assert_eq!("<p>ü</p>", decode_html("<p>ü</p>".as_bytes(), None));
+    let (bytes, _, _) = WINDOWS_1252.encode("<p>café</p>");
+    assert_eq!("<p>café</p>", decode_html(&bytes, None));
+}
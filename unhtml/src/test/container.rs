@@ -0,0 +1,52 @@
+use crate::{
+    scraper::{Html, Selector},
+    Element, Error, InnerHtml, Result, Text,
+};
+use std::collections::{BTreeSet, HashSet, LinkedList, VecDeque};
+use std::rc::Rc;
+
+const HTML: &str = r#"
+<ul>
+    <li> rust </li>
+    <li> html </li>
+    <li> rust </li>
+</ul>
+"#;
+
+#[test]
+fn test_collections() {
+    let selector = Selector::parse("li").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let tags: HashSet<String> = html.select(&selector).inner_text().unwrap();
+    assert_eq!(2, tags.len());
+    assert!(tags.contains("html"));
+    let tags: BTreeSet<String> = html.select(&selector).inner_text().unwrap();
+    assert_eq!(vec!["html", "rust"], tags.iter().collect::<Vec<_>>());
+    let tags: VecDeque<String> = html.select(&selector).inner_text().unwrap();
+    assert_eq!(3, tags.len());
+    let tags: LinkedList<InnerHtml> = html.select(&selector).element().unwrap();
+    assert_eq!(" html ", &*tags.iter().nth(1).unwrap().0);
+}
+
+#[test]
+fn test_array() {
+    let selector = Selector::parse("li").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let tags: [String; 3] = html.select(&selector).inner_text().unwrap();
+    assert_eq!(["rust", "html", "rust"], tags);
+    let result: Result<[String; 2]> = html.select(&selector).inner_text();
+    match result.unwrap_err() {
+        Error::CountMismatch { expected, found } => assert_eq!((2, 3), (expected, found)),
+        err => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn test_pointers() {
+    let selector = Selector::parse("li").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let tag: Box<String> = html.select(&selector).inner_text().unwrap();
+    assert_eq!("rust", *tag);
+    let tags: Vec<Rc<String>> = html.select(&selector).inner_text().unwrap();
+    assert_eq!("html", *tags[1]);
+}
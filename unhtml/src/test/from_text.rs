@@ -164,3 +164,22 @@ fn test_fail_attr() {
     let foo_result: Result<u8> = html.select(&foo_selector).attr("value");
     assert!(!foo_result.is_ok());
 }
+
+#[test]
+fn test_bool() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(
+        r##"
+        <div>
+            <p value="off"> Yes </p>
+            <p value="maybe"> true </p>
+        </div>
+    "##,
+    );
+    let result: Vec<bool> = html.select(&selector).inner_text().unwrap();
+    assert_eq!(vec![true, true], result);
+    let result: bool = html.select(&selector).attr("value").unwrap();
+    assert!(!result);
+    let result: Result<Vec<bool>> = html.select(&selector).attr("value");
+    assert!(result.is_err());
+}
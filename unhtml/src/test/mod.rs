@@ -1,2 +1,11 @@
+// tests spell failures as `!result.is_ok()`
+#![allow(clippy::nonminimal_bool)]
+
+mod charset;
+mod container;
 mod from_html;
 mod from_text;
+mod raw;
+mod select;
+mod source;
+mod table;
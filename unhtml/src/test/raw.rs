@@ -0,0 +1,49 @@
+use crate::{
+    scraper::{ElementRef, Html, Selector},
+    Element, Fragment, FromHtmlRef, InnerHtml, OuterHtml, Result,
+};
+
+const HTML: &str = r#"<div><p>Hello <b>World</b></p></div>"#;
+
+#[test]
+fn test_outer_html() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let source: String = html.select(&selector).element().unwrap();
+    assert_eq!("<p>Hello <b>World</b></p>", source);
+    let outer: OuterHtml = html.select(&selector).element().unwrap();
+    assert_eq!("<p>Hello <b>World</b></p>", &*outer);
+}
+
+#[test]
+fn test_inner_html() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let inner: InnerHtml = html.select(&selector).element().unwrap();
+    assert_eq!("Hello <b>World</b>", &*inner);
+    let result: Result<InnerHtml> = html.select(&Selector::parse("a").unwrap()).element();
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_fragment() {
+    let selector = Selector::parse("p").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let fragment: Fragment = html.select(&selector).element().unwrap();
+    assert_eq!("<p>Hello <b>World</b></p>", fragment.to_string());
+    let bold: Vec<ElementRef> = fragment
+        .html()
+        .select(&Selector::parse("b").unwrap())
+        .collect();
+    assert_eq!(1, bold.len());
+    let inner: InnerHtml = fragment.extract().unwrap();
+    assert_eq!("<p>Hello <b>World</b></p>", &*inner);
+}
+
+#[test]
+fn test_element_ref() {
+    let selector = Selector::parse("b").unwrap();
+    let html = Html::parse_fragment(HTML);
+    let element = ElementRef::from_elements_ref(&mut html.select(&selector)).unwrap();
+    assert_eq!("b", element.value().name());
+}
@@ -0,0 +1,25 @@
+use crate::{
+    scraper::{Html, Selector},
+    Select,
+};
+
+fn texts(start: isize, end: Option<isize>) -> Vec<String> {
+    let html = Html::parse_fragment("<ul><li>0</li><li>1</li><li>2</li><li>3</li></ul>");
+    let selector = Selector::parse("li").unwrap();
+    html.select(&selector)
+        .select_range(start, end)
+        .map(|li| li.text().collect())
+        .collect()
+}
+
+#[test]
+fn test_select_range() {
+    assert_eq!(vec!["1", "2", "3"], texts(1, None));
+    assert_eq!(vec!["1", "2"], texts(1, Some(3)));
+    assert_eq!(vec!["3"], texts(-1, None));
+    assert_eq!(vec!["1", "2"], texts(1, Some(-1)));
+    assert_eq!(vec!["0", "1"], texts(-9, Some(2)));
+    assert!(texts(3, Some(1)).is_empty());
+    assert!(texts(5, None).is_empty());
+    assert!(texts(2, Some(-3)).is_empty());
+}
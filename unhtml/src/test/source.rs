@@ -0,0 +1,93 @@
+use crate::{
+    scraper::{Html, Selector},
+    Error, FromSource, Source, TextMode,
+};
+use regex::Regex;
+
+const HTML: &str = "<div>\n  <p>Hello <b>World</b>&nbsp;!</p><p>Line<br>break</p>\n  <ul><li>a</li><li> b </li></ul>\n</div>";
+
+fn text(mode: TextMode) -> String {
+    let html = Html::parse_fragment(HTML);
+    let div = html
+        .select(&Selector::parse("div").unwrap())
+        .next()
+        .unwrap();
+    mode.text(div)
+}
+
+#[test]
+fn test_text_modes() {
+    assert_eq!(
+        "\n  Hello World\u{a0}!Linebreak\n  a b \n",
+        text(TextMode::Raw)
+    );
+    assert_eq!("HelloWorld!Linebreakab", text(TextMode::Trim));
+    assert_eq!("Hello World ! Line break a b", text(TextMode::Join));
+    assert_eq!("Hello World !Linebreak a b", text(TextMode::Collapse));
+    assert_eq!("Hello World !\nLine\nbreak\na\nb", text(TextMode::Block));
+}
+
+#[test]
+fn test_from_source() {
+    let html = Html::parse_fragment(r#"<p title=" 12 ">1 <b>2</b></p>"#);
+    let selector = Selector::parse("p").unwrap();
+    let text = String::from_source(
+        &mut html.select(&selector),
+        &Source::inner_text(TextMode::Join),
+    )
+    .unwrap();
+    assert_eq!("1 2", text);
+    let number = u8::from_source(&mut html.select(&selector), &Source::attr("title")).unwrap();
+    assert_eq!(12, number);
+    let numbers = Vec::<u8>::from_source(
+        &mut html.select(&selector),
+        &Source::inner_text(TextMode::Join),
+    );
+    assert!(numbers.is_err());
+}
+
+#[test]
+fn test_regex() {
+    let html = Html::parse_fragment(r#"<p title="item-42">Price: 12.50 EUR</p>"#);
+    let selector = Selector::parse("p").unwrap();
+    let regex = Regex::new(r"(?P<amount>[\d.]+) (?P<currency>\w+)").unwrap();
+    let source = Source::inner_text(TextMode::Trim).with_regex(&regex, Some("currency"));
+    let currency = String::from_source(&mut html.select(&selector), &source).unwrap();
+    assert_eq!("EUR", currency);
+    let regex = Regex::new(r"\d+").unwrap();
+    let source = Source::attr("title").with_regex(&regex, None);
+    let id = u32::from_source(&mut html.select(&selector), &source).unwrap();
+    assert_eq!(42, id);
+    let regex = Regex::new(r"(\d+) USD").unwrap();
+    let source = Source::inner_text(TextMode::Trim).with_regex(&regex, None);
+    let err = f64::from_source(&mut html.select(&selector), &source).unwrap_err();
+    assert!(matches!(err, Error::TextParseError { .. }));
+}
+
+#[test]
+fn test_own_text() {
+    let html =
+        Html::parse_fragment("<div>\n  Hello <b>World</b>\n  again<br>next<p>para</p>end</div>");
+    let div = html
+        .select(&Selector::parse("div").unwrap())
+        .next()
+        .unwrap();
+    assert_eq!("\n  Hello \n  againnextend", TextMode::Raw.own_text(div));
+    assert_eq!("Helloagainnextend", TextMode::Trim.own_text(div));
+    assert_eq!("Hello again next end", TextMode::Join.own_text(div));
+    assert_eq!("Hello againnextend", TextMode::Collapse.own_text(div));
+    assert_eq!("Hello again\nnext\nend", TextMode::Block.own_text(div));
+}
+
+#[test]
+fn test_block_table() {
+    let html = Html::parse_fragment(
+        "<table><tr><th>Name</th><th>Price</th></tr><tr><td>Tea</td><td>1.5</td></tr></table>",
+    );
+    let table = html
+        .select(&Selector::parse("table").unwrap())
+        .next()
+        .unwrap();
+    assert_eq!("Name Price\nTea 1.5", TextMode::Block.text(table));
+    assert_eq!("NamePriceTea1.5", TextMode::Collapse.text(table));
+}
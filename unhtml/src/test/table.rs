@@ -0,0 +1,83 @@
+use crate::{
+    scraper::{Html, Selector},
+    Element, FromRow, Result, Row, Table, Text,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+struct Cells(Vec<String>);
+
+impl<'a> FromRow<'a> for Cells {
+    fn from_row(row: &Row<'_, 'a>) -> Result<Self> {
+        let mut cells = Vec::new();
+        for index in 0..row.headers().len() {
+            cells.push(row.cell_at(index).into_iter().inner_text()?);
+        }
+        Ok(Cells(cells))
+    }
+}
+
+fn cells(row: &[&str]) -> Cells {
+    Cells(row.iter().map(ToString::to_string).collect())
+}
+
+#[test]
+fn test_spans() {
+    let html = Html::parse_fragment(
+        r#"
+        <table>
+            <thead>
+                <tr><th rowspan="2">Name</th><th colspan="2">Price</th></tr>
+                <tr><th>Min</th><th>Max</th></tr>
+            </thead>
+            <tbody>
+                <tr><th rowspan="2">Lemon</th><td>1</td><td>2</td></tr>
+                <tr><td colspan="2">3</td></tr>
+            </tbody>
+        </table>
+    "#,
+    );
+    let table: Table<Cells> = html
+        .select(&Selector::parse("table").unwrap())
+        .element()
+        .unwrap();
+    assert_eq!(["Name", "Price Min", "Price Max"], table.headers());
+    assert_eq!(
+        vec![cells(&["Lemon", "1", "2"]), cells(&["Lemon", "3", "3"])],
+        table.into_rows()
+    );
+}
+
+#[test]
+fn test_header_without_thead() {
+    let selector = Selector::parse("div").unwrap();
+    let html = Html::parse_fragment(
+        r#"
+        <div>
+            <table>
+                <tr><th>Name</th><th>Price</th></tr>
+                <tr><td>Lemon</td><td>1</td></tr>
+            </table>
+        </div>
+    "#,
+    );
+    let table: Table<Cells> = html.select(&selector).element().unwrap();
+    assert_eq!(["Name", "Price"], table.headers());
+    assert_eq!([cells(&["Lemon", "1"])], *table);
+}
+
+#[test]
+fn test_missing_cell() {
+    let html = Html::parse_fragment(
+        r#"
+        <table>
+            <tr><th>Name</th><th>Price</th></tr>
+            <tr><td>Lemon</td><td>1</td></tr>
+            <tr><td>Apple</td></tr>
+        </table>
+    "#,
+    );
+    let result: Result<Table<Cells>> = html.select(&Selector::parse("table").unwrap()).element();
+    let context = result.unwrap_err().context().cloned().unwrap();
+    assert_eq!("[1]", context.field_path());
+    assert_eq!("<tr><td>Apple</td></tr>", context.snippet);
+}
@@ -1,5 +1,14 @@
-use crate::Result;
+use crate::stream::HTML_NAMESPACE;
+use crate::{
+    decode_html, BoolVocabulary, Error, Extraction, FromSourceRef, FromTextRef, Items, Result,
+    Source,
+};
+use html5ever::driver;
+use html5ever::tendril::TendrilSink;
+use html5ever::{LocalName, Namespace, QualName};
 use scraper::{ElementRef, Html, Selector};
+use std::io::Read;
+use std::iter;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
 use std::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
@@ -7,12 +16,24 @@ use std::num::{
 };
 use std::path::PathBuf;
 use std::str::FromStr;
+use unhtml_xpath::XPath;
 
 pub trait Select<'b, 'a: 'b> {
     fn select_elements(
         self,
         selector: &'b Selector,
     ) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b>;
+
+    /// elements selected by `xpath` from each element
+    fn select_xpath(self, xpath: &'b XPath) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b>;
+
+    /// elements in `start..end` of the selected elements in document order,
+    /// negative bounds count from the end like `-1` for the last one
+    fn select_range(
+        self,
+        start: isize,
+        end: Option<isize>,
+    ) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b>;
 }
 
 impl<'b, 'a: 'b, T> Select<'b, 'a> for T
@@ -25,6 +46,40 @@ where
     ) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b> {
         Box::new(self.flat_map(move |elem_ref| elem_ref.select(selector)))
     }
+
+    fn select_xpath(self, xpath: &'b XPath) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b> {
+        Box::new(self.flat_map(move |elem_ref| xpath.select(elem_ref)))
+    }
+
+    fn select_range(
+        self,
+        start: isize,
+        end: Option<isize>,
+    ) -> Box<dyn Iterator<Item = ElementRef<'a>> + 'b> {
+        match (start, end) {
+            (0.., None) => Box::new(self.skip(start as usize)),
+            (0.., Some(end @ 0..)) => Box::new(self.take(end as usize).skip(start as usize)),
+            // bounds from the end need the number of elements
+            _ => {
+                let elements: Vec<_> = self.collect();
+                let len = elements.len() as isize;
+                let bound = |index: isize| {
+                    if index < 0 {
+                        (len + index).max(0)
+                    } else {
+                        index.min(len)
+                    }
+                };
+                let (start, end) = (bound(start), bound(end.unwrap_or(len)));
+                Box::new(
+                    elements
+                        .into_iter()
+                        .skip(start as usize)
+                        .take((end - start).max(0) as usize),
+                )
+            }
+        }
+    }
 }
 
 pub type ElemIter<'b, 'a> = &'b mut (dyn Iterator<Item = ElementRef<'a>> + 'b);
@@ -32,20 +87,164 @@ pub type ElemIter<'b, 'a> = &'b mut (dyn Iterator<Item = ElementRef<'a>> + 'b);
 /// parse html
 pub trait FromHtml: Sized {
     fn from_elements(select: ElemIter) -> Result<Self>;
+
+    /// like `from_elements`, but keep going after a failed field
+    /// and return all errors as an `Error::Multiple`
+    fn from_elements_report(select: ElemIter) -> Result<Self> {
+        Self::from_elements(select)
+    }
+
+    /// like `from_elements`, in an extraction shared by all fields,
+    /// implemented by types with fields that need it like `Url`
+    fn from_elements_with(select: ElemIter, _extraction: &Extraction) -> Result<Self> {
+        Self::from_elements(select)
+    }
+
+    fn from_elements_report_with(select: ElemIter, _extraction: &Extraction) -> Result<Self> {
+        Self::from_elements_report(select)
+    }
+
+    /// parse `html` as a document, with implied `html`, `head` and `body` elements
     fn from_html(html: &str) -> Result<Self> {
-        Self::from_elements(
-            &mut Html::parse_document(html).select(&Selector::parse(":root").unwrap()),
-        )
+        Self::from_document(&Html::parse_document(html))
+    }
+
+    fn from_html_report(html: &str) -> Result<Self> {
+        Self::from_document_report(&Html::parse_document(html))
+    }
+
+    /// extract from the root element of a parsed document or fragment,
+    /// so one document can be parsed once and extracted into many types
+    fn from_document(document: &Html) -> Result<Self> {
+        Self::from_elements(&mut iter::once(document.root_element()))
+    }
+
+    fn from_document_report(document: &Html) -> Result<Self> {
+        Self::from_elements_report(&mut iter::once(document.root_element()))
+    }
+
+    /// like `from_html`, but relative `Url` fields are resolved against `base`
+    /// and the `href` of the first `<base>` element, which is resolved against `base` first
+    #[cfg(feature = "url")]
+    fn from_html_with_base(html: &str, base: &url::Url) -> Result<Self> {
+        Self::from_document_with_base(&Html::parse_document(html), base)
+    }
+
+    /// like `from_html_with_base` for a parsed document
+    #[cfg(feature = "url")]
+    fn from_document_with_base(document: &Html, base: &url::Url) -> Result<Self> {
+        Self::from_element_with_base(document.root_element(), base)
+    }
+
+    /// parse `html` as a fragment in `body`, the root element is `html` without `head` or `body`
+    fn from_fragment(html: &str) -> Result<Self> {
+        Self::from_document(&Html::parse_fragment(html))
+    }
+
+    /// like `from_fragment`, but parse `html` in the `context` element,
+    /// like `<tr>` that is dropped outside of `table`, `tbody`, `thead` or `tfoot`
+    fn from_html_in_context(html: &str, context: &str) -> Result<Self> {
+        Self::from_document(&parse_in_context(html, context))
+    }
+
+    /// extract from `element` as the root element, selectors match its descendants
+    fn from_element(element: ElementRef) -> Result<Self> {
+        Self::from_elements(&mut iter::once(element))
+    }
+
+    /// like `from_element`, but relative `Url` fields are resolved like `from_html_with_base`
+    #[cfg(feature = "url")]
+    fn from_element_with_base(element: ElementRef, base: &url::Url) -> Result<Self> {
+        let extraction = Extraction::new().with_base(base.clone());
+        Self::from_elements_with(&mut iter::once(element), &extraction)
+    }
+
+    /// parse html bytes decoded by the charset sniffed like `decode_html`
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Self::from_html(&decode_html(bytes, None))
+    }
+
+    /// like `from_bytes`, with a charset hint from the transport layer,
+    /// like `Shift_JIS` or the `Content-Type` header `text/html; charset=gbk`
+    fn from_bytes_with_charset(bytes: &[u8], charset: &str) -> Result<Self> {
+        Self::from_html(&decode_html(bytes, Some(charset)))
+    }
+
+    /// read all html bytes from `reader` and parse them like `from_bytes`
+    fn from_reader<R: Read>(mut reader: R) -> Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// extract an item from each element of `document` selected by `selector` lazily,
+    /// so the caller can stop early
+    fn iter_html<'a>(document: &'a Html, selector: &'a Selector) -> Items<'a, 'a, Self> {
+        Items::new(document.select(selector), Self::from_elements_with)
+    }
+
+    /// like `iter_html`, but relative `Url` fields are resolved like `from_html_with_base`
+    #[cfg(feature = "url")]
+    fn iter_html_with_base<'a>(
+        document: &'a Html,
+        selector: &'a Selector,
+        base: &url::Url,
+    ) -> Items<'a, 'a, Self> {
+        Items::new(document.select(selector), Self::from_elements_with)
+            .with_extraction(Extraction::new().with_base(base.clone()))
     }
 }
 
 pub trait Element<'b, 'a: 'b, T: 'a> {
     fn element(&'b mut self) -> Result<T>;
+    fn element_report(&'b mut self) -> Result<T>;
 }
 
 pub trait FromText: Sized {
     fn from_inner_text(select: ElemIter) -> Result<Self>;
     fn from_attr(select: ElemIter, attr: &str) -> Result<Self>;
+
+    /// like `from_inner_text`, in the extraction of the html type with this field
+    fn from_inner_text_with(select: ElemIter, _extraction: &Extraction) -> Result<Self> {
+        Self::from_inner_text(select)
+    }
+
+    fn from_attr_with(select: ElemIter, attr: &str, _extraction: &Extraction) -> Result<Self> {
+        Self::from_attr(select, attr)
+    }
+}
+
+/// parse the text taken by a `Source`, required by derived fields with `text_mode`, `regex`,
+/// `own_text`, `inner_html`, `outer_html` or `tag`
+///
+/// It's implemented by `#[derive(FromText)]`, types parsed by `FromStr` and containers of them.
+pub trait FromSource: Sized {
+    /// parse the text taken from the first element by `source`
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self>;
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        _extraction: &Extraction,
+    ) -> Result<Self> {
+        Self::from_source(select, source)
+    }
+}
+
+/// parse a fragment like `Html::parse_fragment`, but in the `context` element instead of `body`
+pub(crate) fn parse_in_context(html: &str, context: &str) -> Html {
+    let context = QualName::new(
+        None,
+        Namespace::from(HTML_NAMESPACE),
+        LocalName::from(context.to_ascii_lowercase()),
+    );
+    driver::parse_fragment(
+        Html::new_fragment(),
+        Default::default(),
+        context,
+        Vec::new(),
+    )
+    .one(html)
 }
 
 pub trait Text<'b, 'a: 'b, T: 'a> {
@@ -61,6 +260,10 @@ where
     fn element(&'b mut self) -> Result<T> {
         T::from_elements(self)
     }
+
+    fn element_report(&'b mut self) -> Result<T> {
+        T::from_elements_report(self)
+    }
 }
 
 impl<'b, 'a: 'b, T, I> Text<'b, 'a, T> for I
@@ -82,17 +285,36 @@ where
     T: FromText,
 {
     fn from_inner_text(select: ElemIter) -> Result<Self> {
-        Ok(match T::from_inner_text(select) {
-            Ok(ret) => Some(ret),
-            Err(_) => None,
-        })
+        Self::from_inner_text_with(select, &Extraction::default())
     }
 
     fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
-        Ok(match T::from_attr(select, attr) {
-            Ok(ret) => Some(ret),
-            Err(_) => None,
-        })
+        Self::from_attr_with(select, attr, &Extraction::default())
+    }
+
+    fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_inner_text_with(select, extraction).ok())
+    }
+
+    fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_attr_with(select, attr, extraction).ok())
+    }
+}
+
+impl<T> FromSource for Option<T>
+where
+    T: FromSource,
+{
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        Self::from_source_with(select, source, &Extraction::default())
+    }
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        Ok(T::from_source_with(select, source, extraction).ok())
     }
 }
 
@@ -101,10 +323,19 @@ where
     T: FromHtml,
 {
     fn from_elements(select: ElemIter) -> Result<Self> {
-        Ok(match T::from_elements(select) {
-            Ok(ret) => Some(ret),
-            Err(_) => None,
-        })
+        Self::from_elements_with(select, &Extraction::default())
+    }
+
+    fn from_elements_report(select: ElemIter) -> Result<Self> {
+        Self::from_elements_report_with(select, &Extraction::default())
+    }
+
+    fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_elements_with(select, extraction).ok())
+    }
+
+    fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        Ok(T::from_elements_report_with(select, extraction).ok())
     }
 }
 
@@ -113,17 +344,55 @@ where
     T: FromText,
 {
     fn from_inner_text(select: ElemIter) -> Result<Self> {
+        Self::from_inner_text_with(select, &Extraction::default())
+    }
+
+    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+        Self::from_attr_with(select, attr, &Extraction::default())
+    }
+
+    fn from_inner_text_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
         let mut ret = vec![];
-        for elem in select {
-            ret.push(vec![elem].into_iter().inner_text()?)
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_inner_text_with(&mut iter::once(elem), extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
         }
         Ok(ret)
     }
 
-    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+    fn from_attr_with(select: ElemIter, attr: &str, extraction: &Extraction) -> Result<Self> {
         let mut ret = vec![];
-        for elem in select {
-            ret.push(vec![elem].into_iter().attr(attr)?)
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_attr_with(&mut iter::once(elem), attr, extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
+        }
+        Ok(ret)
+    }
+}
+
+impl<T> FromSource for Vec<T>
+where
+    T: FromSource,
+{
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        Self::from_source_with(select, source, &Extraction::default())
+    }
+
+    fn from_source_with(
+        select: ElemIter,
+        source: &Source,
+        extraction: &Extraction,
+    ) -> Result<Self> {
+        let mut ret = vec![];
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_source_with(&mut iter::once(elem), source, extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
         }
         Ok(ret)
     }
@@ -134,12 +403,39 @@ where
     T: FromHtml,
 {
     fn from_elements(select: ElemIter) -> Result<Self> {
+        Self::from_elements_with(select, &Extraction::default())
+    }
+
+    fn from_elements_report(select: ElemIter) -> Result<Self> {
+        Self::from_elements_report_with(select, &Extraction::default())
+    }
+
+    fn from_elements_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
         let mut ret = vec![];
-        for elem in select {
-            ret.push(vec![elem].into_iter().element()?)
+        for (index, elem) in select.enumerate() {
+            ret.push(
+                T::from_elements_with(&mut iter::once(elem), extraction)
+                    .map_err(|err| err.at_index(index, &elem))?,
+            )
         }
         Ok(ret)
     }
+
+    fn from_elements_report_with(select: ElemIter, extraction: &Extraction) -> Result<Self> {
+        let mut ret = vec![];
+        let mut errors = vec![];
+        for (index, elem) in select.enumerate() {
+            match T::from_elements_report_with(&mut iter::once(elem), extraction) {
+                Ok(item) => ret.push(item),
+                Err(err) => errors.push(err.at_index(index, &elem)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(ret)
+        } else {
+            Err(Error::multiple(errors))
+        }
+    }
 }
 
 impl FromText for () {
@@ -152,6 +448,62 @@ impl FromText for () {
     }
 }
 
+impl FromSource for () {
+    fn from_source(_select: ElemIter, _source: &Source) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl<'a> FromTextRef<'a> for () {
+    fn from_inner_text_ref(_select: ElemIter<'_, 'a>) -> Result<Self> {
+        Ok(())
+    }
+
+    fn from_attr_ref(_select: ElemIter<'_, 'a>, _attr: &str) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl<'a> FromSourceRef<'a> for () {
+    fn from_source_ref(_select: ElemIter<'_, 'a>, _source: &Source) -> Result<Self> {
+        Ok(())
+    }
+}
+
+/// text in `BoolVocabulary::DEFAULT`, like `yes` or `off`
+impl FromText for bool {
+    fn from_inner_text(select: ElemIter) -> Result<Self> {
+        BoolVocabulary::DEFAULT.parse(&String::from_inner_text(select)?)
+    }
+
+    fn from_attr(select: ElemIter, attr: &str) -> Result<Self> {
+        BoolVocabulary::DEFAULT.parse(&String::from_attr(select, attr)?)
+    }
+}
+
+impl FromSource for bool {
+    fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+        let first = select.next().ok_or(())?;
+        BoolVocabulary::DEFAULT.parse(&source.text(first)?)
+    }
+}
+
+impl<'a> FromTextRef<'a> for bool {
+    fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+        Self::from_inner_text(select)
+    }
+
+    fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+        Self::from_attr(select, attr)
+    }
+}
+
+impl<'a> FromSourceRef<'a> for bool {
+    fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+        Self::from_source(select, source)
+    }
+}
+
 macro_rules! from_text {
     ($($typ:ty),*) => {
         $(
@@ -170,6 +522,29 @@ macro_rules! from_text {
                     Self::from_str(attr.trim()).map_err(|err| (attr.trim().to_owned(), stringify!($typ).to_owned(), err.to_string()).into())
                 }
             }
+
+            impl FromSource for $typ {
+                fn from_source(select: ElemIter, source: &Source) -> Result<Self> {
+                    let first = select.next().ok_or(())?;
+                    let text = source.text(first)?;
+                    Self::from_str(&text).map_err(|err| (text, stringify!($typ).to_owned(), err.to_string()).into())
+                }
+            }
+
+            impl<'a> FromTextRef<'a> for $typ {
+                fn from_inner_text_ref(select: ElemIter<'_, 'a>) -> Result<Self> {
+                    Self::from_inner_text(select)
+                }
+                fn from_attr_ref(select: ElemIter<'_, 'a>, attr: &str) -> Result<Self> {
+                    Self::from_attr(select, attr)
+                }
+            }
+
+            impl<'a> FromSourceRef<'a> for $typ {
+                fn from_source_ref(select: ElemIter<'_, 'a>, source: &Source) -> Result<Self> {
+                    Self::from_source(select, source)
+                }
+            }
         )*
     };
 }
@@ -1,13 +1,65 @@
 use proc_macro2::Span;
+use regex::Regex;
 use scraper::Selector;
 use std::convert::TryFrom;
 use std::fmt::Debug;
-use syn::{parse, punctuated::Punctuated, Attribute, Error, Expr, Ident, LitStr, Result, Token};
+use syn::{
+    parenthesized, parse, punctuated::Punctuated, token, Attribute, Error, Expr, Ident, LitInt,
+    LitStr, Path, Result, Token,
+};
+use unhtml_xpath::XPath;
 
 const HTML_ATTR: &str = "html";
 const SELECTOR_ATTR: &str = "selector";
+const XPATH_ATTR: &str = "xpath";
 const ATTR_ATTR: &str = "attr";
 const DEFAULT_ATTR: &str = "default";
+const OPTIONAL_ATTR: &str = "optional";
+const RENAME_ATTR: &str = "rename";
+const ALIAS_ATTR: &str = "alias";
+const CASE_INSENSITIVE_ATTR: &str = "case_insensitive";
+const TRUTHY_ATTR: &str = "truthy";
+const FALSY_ATTR: &str = "falsy";
+const PRESENCE_ATTR: &str = "presence";
+const KEY_ATTR: &str = "key";
+const VALUE_ATTR: &str = "value";
+const DUPLICATE_ATTR: &str = "duplicate";
+const COLUMN_ATTR: &str = "column";
+const TEXT_MODE_ATTR: &str = "text_mode";
+const REGEX_ATTR: &str = "regex";
+const REGEX_GROUP_ATTR: &str = "regex_group";
+const WITH_ATTR: &str = "with";
+const PARSE_WITH_ATTR: &str = "parse_with";
+const NTH_ATTR: &str = "nth";
+const LAST_ATTR: &str = "last";
+const SKIP_ATTR: &str = "skip";
+const TAKE_ATTR: &str = "take";
+const RANGE_ATTR: &str = "range";
+const STREAM_ATTR: &str = "stream";
+/// keywords taking text from somewhere other than an attribute
+const SOURCES: [&str; 5] = ["text", "own_text", "inner_html", "outer_html", "tag"];
+const TEXT_MODES: [&str; 5] = ["raw", "trim", "join", "collapse", "block"];
+/// attributes taking a string literal
+const STRING_ATTRS: [&str; 15] = [
+    SELECTOR_ATTR,
+    XPATH_ATTR,
+    ATTR_ATTR,
+    RENAME_ATTR,
+    ALIAS_ATTR,
+    TRUTHY_ATTR,
+    FALSY_ATTR,
+    DUPLICATE_ATTR,
+    COLUMN_ATTR,
+    TEXT_MODE_ATTR,
+    REGEX_ATTR,
+    REGEX_GROUP_ATTR,
+    WITH_ATTR,
+    PARSE_WITH_ATTR,
+    RANGE_ATTR,
+];
+/// attributes taking an integer literal
+const INTEGER_ATTRS: [&str; 3] = [NTH_ATTR, SKIP_ATTR, TAKE_ATTR];
+const DUPLICATE_POLICIES: [&str; 3] = ["first", "last", "error"];
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum DefaultAttr {
@@ -19,16 +71,64 @@ pub enum DefaultAttr {
 #[derive(Debug, Eq, PartialEq)]
 pub struct AttrMeta {
     pub selector: Option<String>,
+    pub xpath: Option<String>,
     pub attr: Option<String>,
+    pub source: Option<String>,
     pub default: DefaultAttr,
+    pub optional: bool,
+    pub rename: Option<String>,
+    pub aliases: Vec<String>,
+    pub case_insensitive: bool,
+    pub truthy: Vec<String>,
+    pub falsy: Vec<String>,
+    pub presence: bool,
+    pub key: Option<Box<AttrMeta>>,
+    pub value: Option<Box<AttrMeta>>,
+    pub duplicate: Option<String>,
+    pub column: Option<String>,
+    pub text_mode: Option<String>,
+    pub regex: Option<String>,
+    pub regex_group: Option<String>,
+    pub with: Option<Path>,
+    pub parse_with: Option<Path>,
+    pub nth: Option<usize>,
+    pub last: bool,
+    pub skip: Option<usize>,
+    pub take: Option<usize>,
+    pub range: Option<(isize, Option<isize>)>,
+    pub stream: bool,
 }
 
 impl Default for AttrMeta {
     fn default() -> Self {
         Self {
             selector: None,
+            xpath: None,
             attr: None,
+            source: None,
             default: DefaultAttr::None,
+            optional: false,
+            rename: None,
+            aliases: Vec::new(),
+            case_insensitive: false,
+            truthy: Vec::new(),
+            falsy: Vec::new(),
+            presence: false,
+            key: None,
+            value: None,
+            duplicate: None,
+            column: None,
+            text_mode: None,
+            regex: None,
+            regex_group: None,
+            with: None,
+            parse_with: None,
+            nth: None,
+            last: false,
+            skip: None,
+            take: None,
+            range: None,
+            stream: false,
         }
     }
 }
@@ -41,19 +141,213 @@ impl parse::Parse for AttrMeta {
         for attr in attrs {
             match attr {
                 Attr::Selector(lit_str) => meta.selector = Some(lit_str.value()),
+                Attr::XPath(lit_str) => meta.xpath = Some(lit_str.value()),
                 Attr::Attr(lit_str) => meta.attr = Some(lit_str.value()),
+                Attr::Source(source) => {
+                    if let Some(other) = meta.source.replace(source) {
+                        return Err(input.error(format!(
+                            "`{}` cannot be used with `{}`",
+                            meta.source.unwrap(),
+                            other
+                        )));
+                    }
+                }
                 Attr::Default(def) => meta.default = def,
+                Attr::Optional => meta.optional = true,
+                Attr::Rename(lit_str) => meta.rename = Some(lit_str.value()),
+                Attr::Alias(lit_str) => meta.aliases.push(lit_str.value()),
+                Attr::CaseInsensitive => meta.case_insensitive = true,
+                Attr::Truthy(lit_str) => meta.truthy.push(lit_str.value()),
+                Attr::Falsy(lit_str) => meta.falsy.push(lit_str.value()),
+                Attr::Presence => meta.presence = true,
+                Attr::Key(key) => meta.key = Some(key),
+                Attr::Value(value) => meta.value = Some(value),
+                Attr::Duplicate(lit_str) => meta.duplicate = Some(lit_str.value()),
+                Attr::Column(lit_str) => meta.column = Some(lit_str.value()),
+                Attr::TextMode(lit_str) => meta.text_mode = Some(lit_str.value()),
+                Attr::Regex(lit_str) => meta.regex = Some(lit_str.value()),
+                Attr::RegexGroup(lit_str) => meta.regex_group = Some(lit_str.value()),
+                Attr::With(path) => meta.with = Some(path),
+                Attr::ParseWith(path) => meta.parse_with = Some(path),
+                Attr::Nth(nth) => meta.nth = Some(nth),
+                Attr::Last => meta.last = true,
+                Attr::Skip(skip) => meta.skip = Some(skip),
+                Attr::Take(take) => meta.take = Some(take),
+                Attr::Range(start, end) => meta.range = Some((start, end)),
+                Attr::Stream => meta.stream = true,
             }
         }
+        if meta.selector.is_some() && meta.xpath.is_some() {
+            return Err(input.error("`selector` cannot be used with `xpath`"));
+        }
+        if meta.stream {
+            match &meta.selector {
+                Some(selector) => check_stream_selector(selector, input)?,
+                None => return Err(input.error("`stream` requires a `selector` of items")),
+            }
+        }
+        let positions = [
+            meta.nth.is_some(),
+            meta.last,
+            meta.range.is_some(),
+            meta.skip.is_some() || meta.take.is_some(),
+        ];
+        if positions.iter().filter(|used| **used).count() > 1 {
+            return Err(
+                input.error("only one of `nth`, `last`, `range` or `skip` and `take` can be used")
+            );
+        }
+        if let (Some(_), Some(source)) = (&meta.attr, &meta.source) {
+            return Err(input.error(format!("`attr` cannot be used with `{}`", source)));
+        }
+        if meta.optional && meta.default != DefaultAttr::None {
+            return Err(input.error("`optional` cannot be used with `default`"));
+        }
+        if meta.presence {
+            if !meta.truthy.is_empty() || !meta.falsy.is_empty() {
+                return Err(input.error("`presence` cannot be used with `truthy` or `falsy`"));
+            }
+            if meta.attr.is_none() {
+                return Err(input.error("`presence` requires an `attr` of element"));
+            }
+        }
+        if (!meta.truthy.is_empty() || !meta.falsy.is_empty()) && !meta.extracts_text() {
+            return Err(
+                input.error("`truthy` and `falsy` require an `attr` or a source like `text`")
+            );
+        }
+        if meta.text_mode.is_some()
+            && !matches!(meta.source.as_deref(), Some("text") | Some("own_text"))
+        {
+            return Err(input.error("`text_mode` can only be used with `text` or `own_text`"));
+        }
+        match (&meta.regex, &meta.regex_group) {
+            (Some(_), _) if meta.presence => {
+                return Err(input.error("`regex` cannot be used with `presence`"));
+            }
+            (Some(_), _) if !meta.extracts_text() => {
+                return Err(input.error("`regex` requires an `attr` or a source like `text`"));
+            }
+            (Some(regex), Some(group)) => check_regex_group(regex, group, input)?,
+            (None, Some(_)) => return Err(input.error("`regex_group` requires `regex`")),
+            _ => (),
+        }
+        if meta.with.is_some()
+            && (meta.extracts_text()
+                || meta.presence
+                || meta.key.is_some()
+                || meta.parse_with.is_some()
+                || !meta.truthy.is_empty()
+                || !meta.falsy.is_empty())
+        {
+            return Err(input.error(
+                "`with` receives the selected elements and cannot be used with `attr`, sources like `text`, `presence`, `key`, `value`, `parse_with`, `truthy` or `falsy`",
+            ));
+        }
+        if meta.parse_with.is_some() {
+            if !meta.extracts_text() {
+                return Err(input.error("`parse_with` requires an `attr` or a source like `text`"));
+            }
+            if meta.presence || !meta.truthy.is_empty() || !meta.falsy.is_empty() {
+                return Err(
+                    input.error("`parse_with` cannot be used with `presence`, `truthy` or `falsy`")
+                );
+            }
+        }
+        match (&meta.key, &meta.value) {
+            (Some(key), Some(value)) => {
+                if !key.only_selector_and_source() || !value.only_selector_and_source() {
+                    return Err(input.error(
+                        "only `selector` and `attr` or a source like `text` can be used in `key` and `value`",
+                    ));
+                }
+                if !key.extracts_text() {
+                    return Err(input.error("`key` requires an `attr` or a source like `text`"));
+                }
+                if meta.extracts_text() {
+                    return Err(input.error(
+                        "`attr` and sources like `text` cannot be used with `key` and `value`",
+                    ));
+                }
+            }
+            (None, None) if meta.duplicate.is_some() => {
+                return Err(input.error("`duplicate` requires `key` and `value`"));
+            }
+            (None, None) => (),
+            _ => return Err(input.error("`key` and `value` must be used together")),
+        }
         Ok(meta)
     }
 }
 
+impl AttrMeta {
+    /// text is taken from an attribute or a source keyword
+    pub fn extracts_text(&self) -> bool {
+        self.attr.is_some() || self.source.is_some()
+    }
+
+    /// `rename`, `alias` or `case_insensitive` is used, they only take effect in `FromText` enums
+    pub fn renames(&self) -> bool {
+        self.rename.is_some() || !self.aliases.is_empty() || self.case_insensitive
+    }
+
+    /// elements in `start..end` of the selected elements taken by `nth`, `last`, `range`, `skip` or `take`,
+    /// negative bounds count from the end
+    pub fn range(&self) -> Option<(isize, Option<isize>)> {
+        if let Some(nth) = self.nth {
+            return Some((nth as isize, Some(nth as isize + 1)));
+        }
+        if self.last {
+            return Some((-1, None));
+        }
+        if self.skip.is_some() || self.take.is_some() {
+            let start = self.skip.unwrap_or(0) as isize;
+            return Some((start, self.take.map(|take| start + take as isize)));
+        }
+        self.range
+    }
+
+    fn only_selector_and_source(&self) -> bool {
+        *self
+            == AttrMeta {
+                selector: self.selector.clone(),
+                attr: self.attr.clone(),
+                source: self.source.clone(),
+                ..Default::default()
+            }
+    }
+}
+
 #[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
 enum Attr {
     Selector(LitStr),
+    XPath(LitStr),
     Attr(LitStr),
+    Source(String),
     Default(DefaultAttr),
+    Optional,
+    Rename(LitStr),
+    Alias(LitStr),
+    CaseInsensitive,
+    Truthy(LitStr),
+    Falsy(LitStr),
+    Presence,
+    Key(Box<AttrMeta>),
+    Value(Box<AttrMeta>),
+    Duplicate(LitStr),
+    Column(LitStr),
+    TextMode(LitStr),
+    Regex(LitStr),
+    RegexGroup(LitStr),
+    With(Path),
+    ParseWith(Path),
+    Nth(usize),
+    Last,
+    Skip(usize),
+    Take(usize),
+    Range(isize, Option<isize>),
+    Stream,
 }
 
 impl parse::Parse for Attr {
@@ -69,6 +363,141 @@ impl parse::Parse for Attr {
             }
             // default
             DEFAULT_ATTR => Ok(Attr::Default(DefaultAttr::DefaultImpl)),
+            // optional
+            OPTIONAL_ATTR => Ok(Attr::Optional),
+            // case_insensitive
+            CASE_INSENSITIVE_ATTR => Ok(Attr::CaseInsensitive),
+            // presence
+            PRESENCE_ATTR => Ok(Attr::Presence),
+            // last
+            LAST_ATTR => Ok(Attr::Last),
+            // stream
+            STREAM_ATTR => Ok(Attr::Stream),
+            // nth = N, skip = N, take = N
+            name if INTEGER_ATTRS.contains(&name) && input.peek(Token![=]) => {
+                let _: Token![=] = input.parse()?;
+                let lit_int: LitInt = input
+                    .parse()
+                    .map_err(|err| Error::new(err.span(), "expected integer literal"))?;
+                let value = lit_int
+                    .base10_parse()
+                    .map_err(|_| Error::new(lit_int.span(), "expected a non-negative integer"))?;
+                Ok(match name {
+                    NTH_ATTR => Attr::Nth(value),
+                    SKIP_ATTR => Attr::Skip(value),
+                    _ => Attr::Take(value),
+                })
+            }
+            name if INTEGER_ATTRS.contains(&name) => {
+                Err(input.error(format!("missing '=', expected to find '{} = N'", name)))
+            }
+            // range = "..."
+            RANGE_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                let (start, end) = parse_range(&lit_str.value()).ok_or_else(|| {
+                    Error::new(
+                        lit_str.span(),
+                        "expected a range like `1..`, `..3` or `1..-1`, negative bounds count from the end",
+                    )
+                })?;
+                Ok(Attr::Range(start, end))
+            }
+            // text, own_text, inner_html, outer_html, tag
+            name if SOURCES.contains(&name) => Ok(Attr::Source(name.to_owned())),
+            // key(...)
+            KEY_ATTR if input.peek(token::Paren) => {
+                let content;
+                parenthesized!(content in input);
+                Ok(Attr::Key(Box::new(content.parse()?)))
+            }
+            // value(...)
+            VALUE_ATTR if input.peek(token::Paren) => {
+                let content;
+                parenthesized!(content in input);
+                Ok(Attr::Value(Box::new(content.parse()?)))
+            }
+            KEY_ATTR | VALUE_ATTR => Err(input.error(format!(
+                "expected to find '{}(selector = \"...\", attr = \"...\")'",
+                name
+            ))),
+            // column = "..."
+            COLUMN_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::Column(input.parse()?))
+            }
+            // text_mode = "..."
+            TEXT_MODE_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                if !TEXT_MODES.contains(&&*lit_str.value()) {
+                    return Err(Error::new(
+                        lit_str.span(),
+                        "expected one of `raw`, `trim`, `join`, `collapse`, `block`",
+                    ));
+                }
+                Ok(Attr::TextMode(lit_str))
+            }
+            // regex = "..."
+            REGEX_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                check_regex(&lit_str)?;
+                Ok(Attr::Regex(lit_str))
+            }
+            // regex_group = "..."
+            REGEX_GROUP_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::RegexGroup(input.parse()?))
+            }
+            // with = "...", parse_with = "..."
+            WITH_ATTR | PARSE_WITH_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                let path = lit_str.parse().map_err(|_| {
+                    Error::new(
+                        lit_str.span(),
+                        format!("expected path to a function, found `{}`", lit_str.value()),
+                    )
+                })?;
+                Ok(if name == WITH_ATTR {
+                    Attr::With(path)
+                } else {
+                    Attr::ParseWith(path)
+                })
+            }
+            // duplicate = "..."
+            DUPLICATE_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                if !DUPLICATE_POLICIES.contains(&&*lit_str.value()) {
+                    return Err(Error::new(
+                        lit_str.span(),
+                        "expected one of `first`, `last`, `error`",
+                    ));
+                }
+                Ok(Attr::Duplicate(lit_str))
+            }
+            // truthy = "..."
+            TRUTHY_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::Truthy(input.parse()?))
+            }
+            // falsy = "..."
+            FALSY_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::Falsy(input.parse()?))
+            }
+            // rename = "..."
+            RENAME_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::Rename(input.parse()?))
+            }
+            // alias = "..."
+            ALIAS_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                Ok(Attr::Alias(input.parse()?))
+            }
             // attr = "..."
             ATTR_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
                 let _: Token![=] = input.parse()?;
@@ -87,13 +516,20 @@ impl parse::Parse for Attr {
                 check_selector(&lit_str.value())?;
                 Ok(Attr::Selector(lit_str))
             }
-            ATTR_ATTR | SELECTOR_ATTR if input.peek(Token![=]) => {
+            // xpath = "..."
+            XPATH_ATTR if input.peek(Token![=]) && input.peek2(LitStr) => {
+                let _: Token![=] = input.parse()?;
+                let lit_str: LitStr = input.parse()?;
+                check_xpath(&lit_str)?;
+                Ok(Attr::XPath(lit_str))
+            }
+            name if STRING_ATTRS.contains(&name) && input.peek(Token![=]) => {
                 let _: Token![=] = input.parse()?;
                 Err(input.error("expected string literal"))
             }
-            ATTR_ATTR | SELECTOR_ATTR => Err(input.error(format!(
+            name if STRING_ATTRS.contains(&name) => Err(input.error(format!(
                 "missing '=', expected to find '{} = \"...\"'",
-                name.to_string()
+                name
             ))),
             name => Err(input.error(format!("invalid `html` attribute: {}", name))),
         }
@@ -128,6 +564,61 @@ fn check_selector(selector: &str) -> Result<()> {
     })
 }
 
+/// a selector matched when an element starts, it cannot look at siblings or children after the element
+fn check_stream_selector(selector: &str, input: parse::ParseStream) -> Result<()> {
+    let mut quote = None;
+    let mut brackets = 0;
+    for c in selector.chars() {
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => (),
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '[') => brackets += 1,
+            (None, ']') => brackets -= 1,
+            (None, '+') | (None, '~') | (None, ':') if brackets == 0 => {
+                return Err(input.error(format!(
+                    "`stream` selector `{}` can only use tags, ids, classes, attributes and ` ` or `>` combinators",
+                    selector
+                )));
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn check_xpath(xpath: &LitStr) -> Result<()> {
+    XPath::parse(&xpath.value())
+        .map(|_| ())
+        .map_err(|err| Error::new(xpath.span(), format!("invalid xpath: {}", err)))
+}
+
+/// `start..end` with optional and negative bounds
+fn parse_range(range: &str) -> Option<(isize, Option<isize>)> {
+    let (start, end) = range.split_once("..")?;
+    let bound = |bound: &str| match bound.trim() {
+        "" => Ok(None),
+        bound => bound.parse().map(Some),
+    };
+    Some((bound(start).ok()?.unwrap_or(0), bound(end).ok()?))
+}
+
+fn check_regex(regex: &LitStr) -> Result<()> {
+    Regex::new(&regex.value())
+        .map(|_| ())
+        .map_err(|err| Error::new(regex.span(), format!("invalid regex: {}", err)))
+}
+
+fn check_regex_group(regex: &str, group: &str, input: parse::ParseStream) -> Result<()> {
+    // the regex is already checked by `check_regex`
+    match Regex::new(regex) {
+        Ok(regex) if !regex.capture_names().flatten().any(|name| name == group) => {
+            Err(input.error(format!("no group named `{}` in regex `{}`", group, regex)))
+        }
+        _ => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{AttrMeta, DefaultAttr};
@@ -148,6 +639,7 @@ mod tests {
                 selector: None,
                 attr: None,
                 default: DefaultAttr::None,
+                ..Default::default()
             },
             parse::<ItemStruct>(quote!(
                 #[html]
@@ -166,6 +658,7 @@ mod tests {
                 selector: Some("a".into()),
                 attr: Some("href".into()),
                 default: DefaultAttr::DefaultImpl,
+                ..Default::default()
             },
             parse::<ItemStruct>(quote!(
                 #[html(selector = "a", attr = "href", default)]
@@ -184,6 +677,7 @@ mod tests {
                 selector: Some("a".into()),
                 attr: Some("href".into()),
                 default: DefaultAttr::Value(syn::parse2(quote!(asdf())).unwrap()),
+                ..Default::default()
             },
             parse::<ItemStruct>(quote!(
                 #[html(selector = "a", attr = "href", default = asdf())]
@@ -202,6 +696,7 @@ mod tests {
                 selector: Some("a".into()),
                 attr: Some("href".into()),
                 default: DefaultAttr::Value(syn::parse2(quote!(123)).unwrap()),
+                ..Default::default()
             },
             parse::<ItemStruct>(quote!(
                 #[html(selector = "a", attr = "href", default = 123)]
@@ -227,6 +722,483 @@ mod tests {
         assert!(e.to_string().contains("invalid css"));
     }
 
+    #[test]
+    fn test_parse_meta_optional() {
+        assert_eq!(
+            AttrMeta {
+                selector: Some("a".into()),
+                attr: Some("href".into()),
+                default: DefaultAttr::None,
+                optional: true,
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(selector = "a", attr = "href", optional)]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_meta_optional_with_default() {
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(selector = "a", optional, default)]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+
+        assert!(e
+            .to_string()
+            .contains("`optional` cannot be used with `default`"));
+    }
+
+    #[test]
+    fn test_parse_meta_rename() {
+        assert_eq!(
+            AttrMeta {
+                rename: Some("In stock".into()),
+                aliases: vec!["available".into(), "in-stock".into()],
+                case_insensitive: true,
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(
+                    rename = "In stock",
+                    alias = "available",
+                    alias = "in-stock",
+                    case_insensitive
+                )]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_meta_source() {
+        assert_eq!(
+            AttrMeta {
+                selector: Some("label".into()),
+                source: Some("own_text".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(selector = "label", own_text)]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        assert_eq!(
+            AttrMeta {
+                attr: Some("inner".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(attr = "inner")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, tag)]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`tag` cannot be used with `text`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(attr = "href", inner_html)]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e
+            .to_string()
+            .contains("`attr` cannot be used with `inner_html`"));
+    }
+
+    #[test]
+    fn test_parse_meta_bool() {
+        assert_eq!(
+            AttrMeta {
+                source: Some("text".into()),
+                truthy: vec!["✓".into()],
+                falsy: vec!["✗".into(), "-".into()],
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(text, truthy = "✓", falsy = "✗", falsy = "-")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, presence)]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`presence` requires"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(selector = ".flag", truthy = "yes")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`truthy` and `falsy` require"));
+    }
+
+    #[test]
+    fn test_parse_meta_pairs() {
+        assert_eq!(
+            AttrMeta {
+                selector: Some("tr".into()),
+                key: Some(Box::new(AttrMeta {
+                    selector: Some("th".into()),
+                    source: Some("text".into()),
+                    ..Default::default()
+                })),
+                value: Some(Box::new(AttrMeta {
+                    selector: Some("td".into()),
+                    ..Default::default()
+                })),
+                duplicate: Some("first".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(
+                    selector = "tr",
+                    key(selector = "th", text),
+                    value(selector = "td"),
+                    duplicate = "first"
+                )]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(key(selector = "th"), value(selector = "td"))]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`key` requires an `attr`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(key(text), value(text, optional))]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("only `selector` and `attr`"));
+    }
+
+    #[test]
+    fn test_parse_meta_text_mode() {
+        assert_eq!(
+            AttrMeta {
+                source: Some("text".into()),
+                text_mode: Some("block".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(text, text_mode = "block")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, text_mode = "lines")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("expected one of `raw`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(attr = "title", text_mode = "join")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`text_mode` can only be used"));
+    }
+
+    #[test]
+    fn test_parse_meta_regex() {
+        assert_eq!(
+            AttrMeta {
+                source: Some("text".into()),
+                regex: Some(r"(?P<amount>[\d.]+) EUR".into()),
+                regex_group: Some("amount".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(text, regex = r"(?P<amount>[\d.]+) EUR", regex_group = "amount")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, regex = r"([\d.]+")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("invalid regex"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, regex = r"([\d.]+)", regex_group = "amount")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("no group named `amount`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(regex = r"([\d.]+)")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`regex` requires an `attr`"));
+    }
+
+    #[test]
+    fn test_parse_meta_with() {
+        assert_eq!(
+            AttrMeta {
+                selector: Some("li".into()),
+                with: Some(syn::parse_str("helpers::count").unwrap()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(selector = "li", with = "helpers::count")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        assert_eq!(
+            AttrMeta {
+                attr: Some("title".into()),
+                parse_with: Some(syn::parse_str("parse_date").unwrap()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(attr = "title", parse_with = "parse_date")]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(text, with = "helpers::count")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e
+            .to_string()
+            .contains("`with` receives the selected elements"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(parse_with = "parse_date")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("`parse_with` requires an `attr`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(attr = "title", parse_with = "parse date")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("expected path to a function"));
+    }
+
+    #[test]
+    fn test_parse_meta_xpath() {
+        assert_eq!(
+            AttrMeta {
+                xpath: Some("//dt[text()='Price']/following-sibling::dd[1]".into()),
+                source: Some("text".into()),
+                ..Default::default()
+            },
+            parse::<ItemStruct>(quote!(
+                #[html(xpath = "//dt[text()='Price']/following-sibling::dd[1]", text)]
+                struct A;
+            ))
+            .attrs
+            .try_into()
+            .unwrap()
+        );
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(xpath = "//dt[text()='Price'")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e.to_string().contains("invalid xpath: expected `]`"));
+        let e = AttrMeta::try_from(
+            parse::<ItemStruct>(quote!(
+                #[html(selector = "dd", xpath = "//dd")]
+                struct A;
+            ))
+            .attrs,
+        )
+        .unwrap_err();
+        assert!(e
+            .to_string()
+            .contains("`selector` cannot be used with `xpath`"));
+    }
+
+    #[test]
+    fn test_parse_meta_position() {
+        let meta: AttrMeta = parse::<ItemStruct>(quote!(
+            #[html(selector = "li", nth = 2)]
+            struct A;
+        ))
+        .attrs
+        .try_into()
+        .unwrap();
+        assert_eq!(Some(2), meta.nth);
+        assert_eq!(Some((2, Some(3))), meta.range());
+        let meta: AttrMeta = parse::<ItemStruct>(quote!(
+            #[html(skip = 1, take = 2)]
+            struct A;
+        ))
+        .attrs
+        .try_into()
+        .unwrap();
+        assert_eq!(Some((1, Some(3))), meta.range());
+        let meta: AttrMeta = parse::<ItemStruct>(quote!(
+            #[html(range = "1..-1")]
+            struct A;
+        ))
+        .attrs
+        .try_into()
+        .unwrap();
+        assert_eq!(Some((1, Some(-1))), meta.range());
+        let meta: AttrMeta = parse::<ItemStruct>(quote!(
+            #[html(last)]
+            struct A;
+        ))
+        .attrs
+        .try_into()
+        .unwrap();
+        assert_eq!(Some((-1, None)), meta.range());
+        for (attrs, message) in [
+            (
+                quote!(#[html(nth = 1, last)]),
+                "only one of `nth`, `last`, `range` or `skip` and `take` can be used",
+            ),
+            (
+                quote!(#[html(range = "..", skip = 1)]),
+                "only one of `nth`, `last`, `range` or `skip` and `take` can be used",
+            ),
+            (quote!(#[html(nth = -1)]), "expected a non-negative integer"),
+            (quote!(#[html(take = "2")]), "expected integer literal"),
+            (quote!(#[html(nth)]), "missing '='"),
+            (quote!(#[html(range = "1..=2")]), "expected a range like"),
+            (quote!(#[html(range = "1")]), "expected a range like"),
+        ] {
+            let e = AttrMeta::try_from(
+                parse::<ItemStruct>(quote!(
+                    #attrs
+                    struct A;
+                ))
+                .attrs,
+            )
+            .unwrap_err();
+            assert!(e.to_string().contains(message), "{}", e);
+        }
+    }
+
+    #[test]
+    fn test_parse_meta_stream() {
+        let meta: AttrMeta = parse::<ItemStruct>(quote!(
+            #[html(selector = "#list > li.item[data-id~='a+b']", stream)]
+            struct A;
+        ))
+        .attrs
+        .try_into()
+        .unwrap();
+        assert!(meta.stream);
+        for (attrs, message) in [
+            (
+                quote!(#[html(stream)]),
+                "`stream` requires a `selector` of items",
+            ),
+            (
+                quote!(#[html(selector = "li + li", stream)]),
+                "`stream` selector `li + li` can only use",
+            ),
+            (
+                quote!(#[html(selector = "li:last-child", stream)]),
+                "`stream` selector `li:last-child` can only use",
+            ),
+        ] {
+            let e = AttrMeta::try_from(
+                parse::<ItemStruct>(quote!(
+                    #attrs
+                    struct A;
+                ))
+                .attrs,
+            )
+            .unwrap_err();
+            assert!(e.to_string().contains(message), "{}", e);
+        }
+    }
+
     #[test]
     fn test_parse_meta_bigger() {
         let meta = AttrMeta::try_from(
@@ -1,10 +1,15 @@
 use super::attr_meta::{AttrMeta, DefaultAttr};
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
 use std::convert::TryInto;
-use syn::{parse_macro_input::parse, Attribute, Fields, ItemStruct, Result};
+use syn::{
+    parse_macro_input::parse, Attribute, Data, DeriveInput, Error, Fields, GenericParam, Generics,
+    Ident, Lifetime, LifetimeDef, Result, Variant,
+};
 
-const ATTR_INNER_TEXT: &str = "inner";
+const SOURCE_TEXT: &str = "text";
+const RENAME_ONLY_FROM_TEXT: &str =
+    "`rename`, `alias` and `case_insensitive` can only be used with FromText";
 
 macro_rules! use_idents {
     ($($idents:ident),*) => {
@@ -14,82 +19,691 @@ macro_rules! use_idents {
 
 fn import() -> TokenStream {
     quote!(
-        use unhtml::{scraper::Selector, Element, Text, Select};
+        use unhtml::Select;
     )
 }
 
-// TODO: confirm no lifetime in generics
+/// how the generated code extracts fields
+#[derive(Clone, Copy)]
+struct Mode<'a> {
+    /// keep going after a failed field and collect all errors
+    report: bool,
+    /// lifetime of the document, fields are extracted by `FromHtmlRef` and `FromTextRef`
+    lifetime: Option<&'a Lifetime>,
+    /// fields are extracted from a `unhtml::Row`
+    row: bool,
+}
+
+/// the only lifetime parameter of `target`
+fn document_lifetime(target: &DeriveInput) -> Result<Option<&Lifetime>> {
+    let lifetimes: Vec<_> = target.generics.lifetimes().collect();
+    if lifetimes.len() > 1 {
+        return Err(Error::new(
+            lifetimes[1].lifetime.span(),
+            "FromHtml can only be derived for type with at most one lifetime parameter",
+        ));
+    }
+    Ok(lifetimes.first().map(|def| &def.lifetime))
+}
+
+/// `generics` with a new lifetime `'__unhtml` for the document
+pub fn ref_generics(generics: &Generics) -> Generics {
+    let mut ref_generics = generics.clone();
+    ref_generics.params.insert(
+        0,
+        GenericParam::Lifetime(LifetimeDef::new(Lifetime::new(
+            "'__unhtml",
+            Span::call_site(),
+        ))),
+    );
+    ref_generics
+}
+
 pub fn derive(input: proc_macro::TokenStream) -> Result<TokenStream> {
-    use_idents!(_select);
-    let target = parse::<ItemStruct>(input)?;
+    use_idents!(_select, _extraction);
+    let target = parse::<DeriveInput>(input)?;
     let (impl_generics, ty_generics, where_clause) = target.generics.split_for_impl();
-    let struct_name = target.ident.clone();
-    let attr_meta: AttrMeta = target.attrs.try_into()?;
+    let name = target.ident.clone();
+    let lifetime = document_lifetime(&target)?;
+    let attr_meta: AttrMeta = target.attrs.clone().try_into()?;
+    if attr_meta.renames() {
+        return Err(Error::new(Span::call_site(), RENAME_ONLY_FROM_TEXT));
+    }
     let import_statement = import();
-    let define_elements_statement = define_elements(attr_meta.selector.as_ref());
-    let struct_field_values = gen_struct_field_values(&target.fields)?;
-    let struct_value = match &target.fields {
-        Fields::Named(_) => quote!(Self{#struct_field_values}),
-        Fields::Unnamed(_) => quote!(Self(#struct_field_values)),
-        Fields::Unit => quote!(Self),
-    };
+    let define_elements_statement = define_elements(&attr_meta);
+    let body = gen_data_body(
+        &target,
+        Mode {
+            report: false,
+            lifetime,
+            row: false,
+        },
+    )?;
+    let report_body = gen_data_body(
+        &target,
+        Mode {
+            report: true,
+            lifetime,
+            row: false,
+        },
+    )?;
+    let stream_impl = gen_stream_impl(&target, &attr_meta, &body)?;
+    if let Some(lifetime) = lifetime {
+        return Ok(quote!(
+            impl #impl_generics unhtml::FromHtmlRef<#lifetime> for #name #ty_generics #where_clause {
+                fn from_elements_ref(#_select: unhtml::ElemIter<'_, #lifetime>) -> unhtml::Result<Self> {
+                    <Self as unhtml::FromHtmlRef<#lifetime>>::from_elements_ref_with(#_select, &unhtml::Extraction::default())
+                }
+
+                fn from_elements_ref_report(#_select: unhtml::ElemIter<'_, #lifetime>) -> unhtml::Result<Self> {
+                    <Self as unhtml::FromHtmlRef<#lifetime>>::from_elements_ref_report_with(#_select, &unhtml::Extraction::default())
+                }
+
+                fn from_elements_ref_with(
+                    #_select: unhtml::ElemIter<'_, #lifetime>,
+                    #_extraction: &unhtml::Extraction,
+                ) -> unhtml::Result<Self> {
+                    #import_statement
+                    #define_elements_statement
+                    #body
+                }
+
+                fn from_elements_ref_report_with(
+                    #_select: unhtml::ElemIter<'_, #lifetime>,
+                    #_extraction: &unhtml::Extraction,
+                ) -> unhtml::Result<Self> {
+                    #import_statement
+                    #define_elements_statement
+                    #report_body
+                }
+            }
+        ));
+    }
+
+    // owned types can also be extracted by `FromHtmlRef`
+    let ref_generics = ref_generics(&target.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
     Ok(quote!(
-        impl #impl_generics unhtml::FromHtml for #struct_name #ty_generics #where_clause {
+        impl #impl_generics unhtml::FromHtml for #name #ty_generics #where_clause {
             fn from_elements(#_select: unhtml::ElemIter) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements_with(#_select, &unhtml::Extraction::default())
+            }
+
+            fn from_elements_report(#_select: unhtml::ElemIter) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements_report_with(#_select, &unhtml::Extraction::default())
+            }
+
+            fn from_elements_with(
+                #_select: unhtml::ElemIter,
+                #_extraction: &unhtml::Extraction,
+            ) -> unhtml::Result<Self> {
+                #import_statement
+                #define_elements_statement
+                #body
+            }
+
+            fn from_elements_report_with(
+                #_select: unhtml::ElemIter,
+                #_extraction: &unhtml::Extraction,
+            ) -> unhtml::Result<Self> {
                 #import_statement
                 #define_elements_statement
-                Ok(#struct_value)
+                #report_body
             }
         }
+
+        impl #ref_impl_generics unhtml::FromHtmlRef<'__unhtml> for #name #ty_generics #where_clause {
+            fn from_elements_ref(#_select: unhtml::ElemIter<'_, '__unhtml>) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements(#_select)
+            }
+
+            fn from_elements_ref_report(#_select: unhtml::ElemIter<'_, '__unhtml>) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements_report(#_select)
+            }
+
+            fn from_elements_ref_with(
+                #_select: unhtml::ElemIter<'_, '__unhtml>,
+                #_extraction: &unhtml::Extraction,
+            ) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements_with(#_select, #_extraction)
+            }
+
+            fn from_elements_ref_report_with(
+                #_select: unhtml::ElemIter<'_, '__unhtml>,
+                #_extraction: &unhtml::Extraction,
+            ) -> unhtml::Result<Self> {
+                <Self as unhtml::FromHtml>::from_elements_report_with(#_select, #_extraction)
+            }
+        }
+
+        #stream_impl
     ))
 }
 
-fn define_elements(selector: Option<&String>) -> TokenStream {
-    use_idents!(_select, _elements);
-    let current_select = match selector {
-        Some(selector) => quote!(#_select.select_elements(&Selector::parse(#selector).unwrap())),
-        None => quote!(#_select),
+/// `FromHtmlStream` of a target with `stream`, items are extracted by `body` from their elements
+fn gen_stream_impl(
+    target: &DeriveInput,
+    meta: &AttrMeta,
+    body: &TokenStream,
+) -> Result<TokenStream> {
+    use_idents!(_select, _elements, _extraction);
+    let selector = match meta.selector.as_ref() {
+        Some(selector) if meta.stream => gen_selector(selector),
+        _ => return Ok(quote!()),
+    };
+    if target.generics.lifetimes().next().is_some() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`stream` cannot be used on type with lifetime, items are dropped after extracted",
+        ));
+    }
+    if meta.range().is_some() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`stream` cannot be used with `nth`, `last`, `range`, `skip` or `take`",
+        ));
+    }
+    let name = &target.ident;
+    let (impl_generics, ty_generics, where_clause) = target.generics.split_for_impl();
+    let import_statement = import();
+    Ok(quote!(
+        impl #impl_generics unhtml::FromHtmlStream for #name #ty_generics #where_clause {
+            fn item_selector() -> &'static unhtml::scraper::Selector {
+                #selector
+            }
+
+            fn from_item(#_select: unhtml::ElemIter) -> unhtml::Result<Self> {
+                #import_statement
+                let #_elements: Vec<_> = #_select.collect();
+                let #_extraction = &unhtml::Extraction::default();
+                #body
+            }
+        }
+    ))
+}
+
+pub fn derive_row(input: proc_macro::TokenStream) -> Result<TokenStream> {
+    use_idents!(_row, _elements, _extraction);
+    let target = parse::<DeriveInput>(input)?;
+    let (impl_generics, ty_generics, where_clause) = target.generics.split_for_impl();
+    let name = target.ident.clone();
+    let lifetime = document_lifetime(&target)?;
+    let attr_meta: AttrMeta = target.attrs.clone().try_into()?;
+    if attr_meta.selector.is_some() || attr_meta.xpath.is_some() {
+        return Err(Error::new(
+            Span::call_site(),
+            "`selector` or `xpath` cannot be used on FromRow, select the table instead",
+        ));
+    }
+    let fields = match &target.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "FromRow can only be derived for struct",
+            ))
+        }
+    };
+    let import_statement = import();
+    let body = gen_body(
+        &name.to_string(),
+        quote!(Self),
+        fields,
+        Mode {
+            report: false,
+            lifetime,
+            row: true,
+        },
+    )?;
+    let row_body = quote!(
+        #import_statement
+        let #_elements: Vec<_> = vec![#_row.element()];
+        #body
+    );
+    Ok(match lifetime {
+        Some(lifetime) => quote!(
+            impl #impl_generics unhtml::FromRow<#lifetime> for #name #ty_generics #where_clause {
+                fn from_row(#_row: &unhtml::Row<'_, #lifetime>) -> unhtml::Result<Self> {
+                    <Self as unhtml::FromRow<#lifetime>>::from_row_with(#_row, &unhtml::Extraction::default())
+                }
+
+                fn from_row_with(
+                    #_row: &unhtml::Row<'_, #lifetime>,
+                    #_extraction: &unhtml::Extraction,
+                ) -> unhtml::Result<Self> {
+                    #row_body
+                }
+            }
+        ),
+        None => {
+            let ref_generics = ref_generics(&target.generics);
+            let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
+            quote!(
+                impl #ref_impl_generics unhtml::FromRow<'__unhtml> for #name #ty_generics #where_clause {
+                    fn from_row(#_row: &unhtml::Row<'_, '__unhtml>) -> unhtml::Result<Self> {
+                        <Self as unhtml::FromRow<'__unhtml>>::from_row_with(#_row, &unhtml::Extraction::default())
+                    }
+
+                    fn from_row_with(
+                        #_row: &unhtml::Row<'_, '__unhtml>,
+                        #_extraction: &unhtml::Extraction,
+                    ) -> unhtml::Result<Self> {
+                        #row_body
+                    }
+                }
+            )
+        }
+    })
+}
+
+fn gen_data_body(target: &DeriveInput, mode: Mode) -> Result<TokenStream> {
+    let name = &target.ident;
+    match &target.data {
+        Data::Struct(data) => gen_body(&name.to_string(), quote!(Self), &data.fields, mode),
+        Data::Enum(data) => gen_enum_body(name, data.variants.iter(), mode),
+        Data::Union(_) => Err(Error::new(
+            Span::call_site(),
+            "FromHtml cannot be derived for union",
+        )),
+    }
+}
+
+/// try variants in order and return the first one extracted successfully
+fn gen_enum_body<'a>(
+    enum_name: &Ident,
+    variants: impl Iterator<Item = &'a Variant>,
+    mode: Mode,
+) -> Result<TokenStream> {
+    use_idents!(_elements, _variant, _variant_errors);
+    let mut statements = quote!();
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let variant_name = variant_ident.to_string();
+        let meta: AttrMeta = variant.attrs.clone().try_into()?;
+        if meta.extracts_text()
+            || meta.default != DefaultAttr::None
+            || meta.optional
+            || meta.presence
+            || !meta.truthy.is_empty()
+            || !meta.falsy.is_empty()
+            || meta.key.is_some()
+            || meta.column.is_some()
+            || meta.with.is_some()
+            || meta.stream
+            || meta.renames()
+        {
+            return Err(Error::new(
+                variant_ident.span(),
+                "only `selector` or `xpath` can be used on enum variant",
+            ));
+        }
+        let define_elements = if meta.selector.is_some() || meta.xpath.is_some() {
+            let select = gen_select(quote!(#_elements.clone().into_iter()), &meta);
+            quote!(
+                let #_elements: Vec<_> = #select.collect();
+                if #_elements.is_empty() {
+                    return Err(unhtml::Error::SourceNotFound);
+                }
+            )
+        } else {
+            quote!()
+        };
+        let body = gen_body(
+            &format!("{}::{}", enum_name, variant_name),
+            quote!(Self::#variant_ident),
+            &variant.fields,
+            mode,
+        )?;
+        statements = quote!(
+            #statements
+            let #_variant = || -> unhtml::Result<Self> {
+                #define_elements
+                #body
+            };
+            match #_variant() {
+                Ok(value) => return Ok(value),
+                Err(err) => #_variant_errors.push((#variant_name.to_owned(), err)),
+            }
+        );
+    }
+    let enum_name = enum_name.to_string();
+    Ok(quote!(
+        let mut #_variant_errors: Vec<(String, unhtml::Error)> = Vec::new();
+        #statements
+        Err(unhtml::Error::NoVariantMatched {
+            enum_name: #enum_name.to_owned(),
+            errors: #_variant_errors,
+        })
+    ))
+}
+
+/// extract all fields and construct `constructor`
+fn gen_body(
+    type_name: &str,
+    constructor: TokenStream,
+    fields: &Fields,
+    mode: Mode,
+) -> Result<TokenStream> {
+    use_idents!(_errors);
+    let FieldsCode { statements, values } = gen_fields(type_name, fields, mode)?;
+    let value = gen_struct_value(constructor, fields, &values);
+    Ok(if mode.report {
+        quote!(
+            let mut #_errors: Vec<unhtml::Error> = Vec::new();
+            #statements
+            if !#_errors.is_empty() {
+                return Err(unhtml::Error::multiple(#_errors));
+            }
+            Ok(#value)
+        )
+    } else {
+        quote!(
+            #statements
+            Ok(#value)
+        )
+    })
+}
+
+/// a `&'static Selector` parsed only once
+fn gen_selector(selector: &str) -> TokenStream {
+    quote!({
+        static SELECTOR: unhtml::LazySelector = unhtml::LazySelector::new(#selector);
+        &*SELECTOR
+    })
+}
+
+/// select by `selector` or `xpath` of `meta` from `select`, then take positions like `nth`
+fn gen_select(select: TokenStream, meta: &AttrMeta) -> TokenStream {
+    let select = match (meta.selector.as_ref(), meta.xpath.as_ref()) {
+        (Some(selector), _) => {
+            let selector = gen_selector(selector);
+            quote!(#select.select_elements(#selector))
+        }
+        (None, Some(xpath)) => quote!(#select.select_xpath({
+            static XPATH: unhtml::LazyXPath = unhtml::LazyXPath::new(#xpath);
+            &*XPATH
+        })),
+        (None, None) => select,
     };
+    match meta.range() {
+        Some((start, end)) => {
+            let end = match end {
+                Some(end) => quote!(Some(#end)),
+                None => quote!(None),
+            };
+            quote!(#select.select_range(#start, #end))
+        }
+        None => select,
+    }
+}
+
+fn define_elements(meta: &AttrMeta) -> TokenStream {
+    use_idents!(_select, _elements);
+    let current_select = gen_select(quote!(#_select), meta);
     quote!(let #_elements: Vec<_> = #current_select.collect();)
 }
 
-fn gen_struct_field_values(fields: &Fields) -> Result<TokenStream> {
-    let mut field_pairs = quote!();
-    for field in fields {
-        let value = gen_field_value(field.attrs.clone())?;
-        let next_field = match field.ident.as_ref() {
-            Some(ident) => quote!(#ident: #value),
-            None => quote!(#value),
+struct FieldsCode {
+    // statements binding each field value to a local variable
+    statements: TokenStream,
+    // values of fields in order
+    values: Vec<TokenStream>,
+}
+
+fn gen_struct_value(
+    constructor: TokenStream,
+    fields: &Fields,
+    values: &[TokenStream],
+) -> TokenStream {
+    match fields {
+        Fields::Named(_) => {
+            let idents = fields.iter().map(|field| field.ident.as_ref());
+            quote!(#constructor{#(#idents: #values,)*})
+        }
+        Fields::Unnamed(_) => quote!(#constructor(#(#values,)*)),
+        Fields::Unit => quote!(#constructor),
+    }
+}
+
+fn gen_fields(type_name: &str, fields: &Fields, mode: Mode) -> Result<FieldsCode> {
+    use_idents!(_errors);
+    let mut statements = quote!();
+    let mut values = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let field_name = match field.ident.as_ref() {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
         };
-        field_pairs = quote!(#field_pairs #next_field, );
+        let value = gen_field_value(type_name, &field_name, field.attrs.clone(), mode)?;
+        let variable = format_ident!("_field_{}", index);
+        if mode.report {
+            statements = quote!(
+                #statements
+                let #variable = match #value {
+                    Ok(value) => Some(value),
+                    Err(err) => {
+                        #_errors.push(err);
+                        None
+                    }
+                };
+            );
+            values.push(quote!(#variable.unwrap()));
+        } else {
+            statements = quote!(#statements let #variable = #value?;);
+            values.push(quote!(#variable));
+        }
     }
-    Ok(field_pairs)
+    Ok(FieldsCode { statements, values })
 }
 
-fn gen_field_value(attr: Vec<Attribute>) -> Result<TokenStream> {
-    use_idents!(_elements);
-    let meta: AttrMeta = attr.try_into()?;
-    let current_select = quote!(#_elements.clone().into_iter());
-    let new_select = match meta.selector.as_ref() {
-        Some(selector) => quote!(
-            #current_select.select_elements(&Selector::parse(#selector).unwrap())
+/// extract a value from `select` by `FromHtml` or `FromText` in the extraction of the target
+fn gen_extract(select: TokenStream, meta: &AttrMeta, mode: Mode) -> TokenStream {
+    use_idents!(_extraction);
+    let source = meta.source.as_deref();
+    if meta.text_mode.is_some()
+        || meta.regex.is_some()
+        || matches!(source, Some(source) if source != SOURCE_TEXT)
+    {
+        return gen_extract_source(select, gen_source(meta), mode);
+    }
+    match (mode.lifetime, meta.attr.as_ref()) {
+        (None, _) if source.is_some() => quote!(
+            <_ as unhtml::FromText>::from_inner_text_with(&mut #select, #_extraction)
+        ),
+        (None, Some(attr)) => quote!(
+            <_ as unhtml::FromText>::from_attr_with(&mut #select, #attr, #_extraction)
+        ),
+        (None, None) if mode.report => quote!(
+            <_ as unhtml::FromHtml>::from_elements_report_with(&mut #select, #_extraction)
         ),
-        None => quote!(#current_select),
+        (None, None) => quote!(
+            <_ as unhtml::FromHtml>::from_elements_with(&mut #select, #_extraction)
+        ),
+        (Some(lifetime), _) if source.is_some() => quote!(
+            <_ as unhtml::FromTextRef<#lifetime>>::from_inner_text_ref_with(&mut #select, #_extraction)
+        ),
+        (Some(lifetime), Some(attr)) => quote!(
+            <_ as unhtml::FromTextRef<#lifetime>>::from_attr_ref_with(&mut #select, #attr, #_extraction)
+        ),
+        (Some(lifetime), None) if mode.report => quote!(
+            <_ as unhtml::FromHtmlRef<#lifetime>>::from_elements_ref_report_with(&mut #select, #_extraction)
+        ),
+        (Some(lifetime), None) => quote!(
+            <_ as unhtml::FromHtmlRef<#lifetime>>::from_elements_ref_with(&mut #select, #_extraction)
+        ),
+    }
+}
+
+/// extract a value from `select` by `FromSource`
+fn gen_extract_source(select: TokenStream, source: TokenStream, mode: Mode) -> TokenStream {
+    use_idents!(_extraction);
+    match mode.lifetime {
+        None => quote!(
+            <_ as unhtml::FromSource>::from_source_with(&mut #select, &#source, #_extraction)
+        ),
+        Some(lifetime) => quote!(
+            <_ as unhtml::FromSourceRef<#lifetime>>::from_source_ref_with(&mut #select, &#source, #_extraction)
+        ),
+    }
+}
+
+/// an `unhtml::Source` of the field with its `text_mode` and `regex`
+fn gen_source(meta: &AttrMeta) -> TokenStream {
+    let text_mode = match meta.text_mode.as_deref() {
+        Some("raw") => quote!(Raw),
+        Some("join") => quote!(Join),
+        Some("collapse") => quote!(Collapse),
+        Some("block") => quote!(Block),
+        _ => quote!(Trim),
+    };
+    let source = match (meta.source.as_deref(), meta.attr.as_ref()) {
+        (Some("own_text"), _) => quote!(unhtml::Source::own_text(unhtml::TextMode::#text_mode)),
+        (Some("inner_html"), _) => quote!(unhtml::Source::inner_html()),
+        (Some("outer_html"), _) => quote!(unhtml::Source::outer_html()),
+        (Some("tag"), _) => quote!(unhtml::Source::tag()),
+        (None, Some(attr)) => quote!(unhtml::Source::attr(#attr)),
+        _ => quote!(unhtml::Source::inner_text(unhtml::TextMode::#text_mode)),
+    };
+    match meta.regex.as_ref() {
+        Some(regex) => {
+            let group = match meta.regex_group.as_ref() {
+                Some(group) => quote!(Some(#group)),
+                None => quote!(None),
+            };
+            quote!(#source.with_regex(
+                {
+                    static REGEX: unhtml::LazyRegex = unhtml::LazyRegex::new(#regex);
+                    &*REGEX
+                },
+                #group,
+            ))
+        }
+        None => source,
+    }
+}
+
+/// extract a map by `unhtml::PairSelector`, each selected element is a row of keys and values
+fn gen_pairs(
+    select: TokenStream,
+    key: &AttrMeta,
+    value: &AttrMeta,
+    duplicate: Option<&str>,
+    mode: Mode,
+) -> TokenStream {
+    use_idents!(_key, _value);
+    let selector = |meta: &AttrMeta| match meta.selector.as_ref() {
+        Some(selector) => {
+            let selector = gen_selector(selector);
+            quote!(Some(#selector))
+        }
+        None => quote!(None),
+    };
+    let (key_selector, value_selector) = (selector(key), selector(value));
+    let on_duplicate = match duplicate {
+        Some("first") => quote!(First),
+        Some("last") => quote!(Last),
+        _ => quote!(Error),
+    };
+    let key_value = gen_extract(quote!(#_key), key, mode);
+    let value_value = gen_extract(quote!(#_value), value, mode);
+    quote!(
+        unhtml::PairSelector::new(#key_selector, #value_selector, unhtml::OnDuplicate::#on_duplicate)
+            .extract(&mut #select, |mut #_key| #key_value, |mut #_value| #value_value)
+    )
+}
+
+/// generate an expression of `unhtml::Result<FieldType>`
+fn gen_field_value(
+    struct_name: &str,
+    field_name: &str,
+    attr: Vec<Attribute>,
+    mode: Mode,
+) -> Result<TokenStream> {
+    use_idents!(_elements, _selected, _row);
+    let meta: AttrMeta = attr.try_into()?;
+    if meta.stream {
+        return Err(Error::new(
+            Span::call_site(),
+            "`stream` can only be used on derive target",
+        ));
+    }
+    if meta.renames() {
+        return Err(Error::new(Span::call_site(), RENAME_ONLY_FROM_TEXT));
+    }
+    let current_select = match meta.column.as_ref() {
+        Some(column) if mode.row => quote!(#_row.cell(#column).into_iter()),
+        Some(_) => {
+            return Err(Error::new(
+                Span::call_site(),
+                "`column` can only be used with FromRow",
+            ))
+        }
+        None => quote!(#_elements.clone().into_iter()),
     };
+    let new_select = gen_select(current_select, &meta);
 
-    let result = match meta.attr.as_ref() {
-        Some(attr) if attr == ATTR_INNER_TEXT => quote!(#new_select.inner_text()),
-        Some(attr) => quote!(#new_select.attr(#attr)),
-        None => quote!(#new_select.element()),
+    let select = if meta.optional {
+        quote!(#_selected.into_iter())
+    } else {
+        new_select.clone()
+    };
+
+    let mut result = if meta.presence {
+        let attr = meta.attr.as_ref();
+        quote!(unhtml::Result::Ok(
+            #select.next().map_or(false, |elem| elem.value().attr(#attr).is_some())
+        ))
+    } else if let (Some(key), Some(value)) = (&meta.key, &meta.value) {
+        gen_pairs(select, key, value, meta.duplicate.as_deref(), mode)
+    } else if let Some(with) = meta.with.as_ref() {
+        quote!(#with(&mut #select))
+    } else if let Some(parse_with) = meta.parse_with.as_ref() {
+        let source = gen_source(&meta);
+        quote!(#source.parse_with(&mut #select, #parse_with))
+    } else {
+        gen_extract(select, &meta, mode)
+    };
+
+    if !meta.truthy.is_empty() || !meta.falsy.is_empty() {
+        let (truthy, falsy) = (&meta.truthy, &meta.falsy);
+        result = quote!(unhtml::FromVocabulary::from_vocabulary(
+            #result,
+            &unhtml::BoolVocabulary::new(&[#(#truthy),*], &[#(#falsy),*]),
+        ));
+    }
+
+    // only nothing selected or attr not found is mapped to `None`
+    if meta.optional {
+        result = quote!({
+            let #_selected: Vec<_> = #new_select.collect();
+            if #_selected.is_empty() {
+                unhtml::Result::Ok(None)
+            } else {
+                match #result {
+                    Ok(value) => Ok(Some(value)),
+                    Err(err) if err.is_not_found() => Ok(None),
+                    Err(err) => Err(err),
+                }
+            }
+        });
+    }
+
+    let selector = match meta.selector.as_ref().or(meta.xpath.as_ref()) {
+        Some(selector) => quote!(Some(#selector)),
+        None => quote!(None),
     };
 
     Ok(match meta.default {
-        DefaultAttr::None => quote!(#result?),
+        DefaultAttr::None => quote!(
+            #result.map_err(|err: unhtml::Error| {
+                err.in_field(#struct_name, #field_name, #selector, &#_elements)
+            })
+        ),
         DefaultAttr::DefaultImpl => quote!(
-            #result.unwrap_or_else(|_| ::core::default::Default::default())
+            unhtml::Result::Ok(#result.unwrap_or_else(|_| ::core::default::Default::default()))
+        ),
+        DefaultAttr::Value(expr) => quote!(
+            unhtml::Result::Ok(#result.unwrap_or_else(|_| ::core::convert::From::from(#expr)))
         ),
-        DefaultAttr::Value(expr) => {
-            quote!(#result.unwrap_or_else(|_| ::core::convert::From::from(#expr)))
-        }
     })
 }
@@ -33,16 +33,106 @@
 //!         * [literal type](#literal-type-2)
 //!         * [specification](#specification-3)
 //!         * [default behavior](#default-behavior-2)
+//!     * [optional](#optional)
+//!         * [target](#target-4)
+//!         * [specification](#specification-4)
+//!         * [default behavior](#default-behavior-3)
+//!     * [truthy and falsy](#truthy-and-falsy)
+//!         * [target](#target-5)
+//!         * [literal type](#literal-type-3)
+//!         * [specification](#specification-5)
+//!         * [default behavior](#default-behavior-4)
+//!     * [presence](#presence)
+//!         * [target](#target-6)
+//!         * [specification](#specification-6)
+//!     * [key and value](#key-and-value)
+//!         * [target](#target-7)
+//!         * [specification](#specification-7)
+//!         * [default behavior](#default-behavior-5)
+//!     * [text_mode](#text_mode)
+//!         * [target](#target-8)
+//!         * [literal type](#literal-type-4)
+//!         * [specification](#specification-8)
+//!         * [default behavior](#default-behavior-6)
+//!     * [regex](#regex)
+//!         * [target](#target-9)
+//!         * [literal type](#literal-type-5)
+//!         * [specification](#specification-9)
+//!     * [with and parse_with](#with-and-parse_with)
+//!         * [target](#target-10)
+//!         * [literal type](#literal-type-6)
+//!         * [specification](#specification-10)
+//!     * [text, own_text, inner_html, outer_html and tag](#text-own_text-inner_html-outer_html-and-tag)
+//!         * [target](#target-11)
+//!         * [specification](#specification-11)
+//!         * [default behavior](#default-behavior-7)
+//!     * [xpath](#xpath)
+//!         * [target](#target-12)
+//!         * [literal type](#literal-type-7)
+//!         * [specification](#specification-12)
+//!         * [default behavior](#default-behavior-8)
+//!     * [nth, last, skip, take and range](#nth-last-skip-take-and-range)
+//!         * [target](#target-13)
+//!         * [literal type](#literal-type-8)
+//!         * [specification](#specification-13)
+//!         * [default behavior](#default-behavior-9)
+//!     * [stream](#stream)
+//!         * [target](#target-14)
+//!         * [specification](#specification-14)
 //! * [Field Type](#field-type)
 //!     * [any type implemented FromHtml, without generics](#any-type-implemented-fromhtml-without-generics)
 //!     * [Vec](#vec)
+//!     * [containers](#containers)
+//!     * [Lenient](#lenient)
+//!     * [Url](#url)
+//!     * [raw html](#raw-html)
+//!     * [borrowed fields](#borrowed-fields)
+//! * [FromText](#fromtext)
+//! * [FromRow](#fromrow)
+//! * [Items](#items)
+//! * [Deserialize](#deserialize)
 //! * [Source HTML](#source-html)
-//!     * [with top selector](#with-top-selector)
-//!     * [without top selector](#without-top-selector)
+//!     * [document](#document)
+//!     * [fragment](#fragment)
+//!     * [parsed document and elements](#parsed-document-and-elements)
+//!     * [bytes](#bytes)
 //!
 //! ### Derive Target
 //!
-//! `struct`
+//! `struct` or `enum`
+//!
+//! ##### enum
+//!
+//! each variant can have its own `selector`, variants are tried in order and the first one extracted successfully is returned.
+//! A variant with a `selector` fails when nothing is selected, a unit variant without `selector` always matches.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml, Debug, Eq, PartialEq)]
+//! enum Item {
+//!     #[html(selector = "a")]
+//!     Link {
+//!         #[html(attr = "href")]
+//!         href: String,
+//!     },
+//!
+//!     #[html(selector = "img")]
+//!     Image(#[html(attr = "src")] String),
+//!
+//!     #[html(selector = ".sold-out")]
+//!     SoldOut,
+//! }
+//!
+//! assert_eq!(Item::Image("lemon.png".into()), Item::from_html(r#"<img src="lemon.png">"#).unwrap());
+//! assert_eq!(Item::SoldOut, Item::from_html(r#"<span class="sold-out"></span>"#).unwrap());
+//! ```
+//!
+//! if all variants fail, an `Error::NoVariantMatched` with the error of each variant is returned.
+//!
 //!
 //! ### Basic Usage
 //!
@@ -55,13 +145,13 @@
 //! #[derive(FromHtml)]
 //! #[html(selector = "#test")]
 //! struct SingleUser {
-//!     #[html(selector = "p:nth-child(1)", attr = "inner")]
+//!     #[html(selector = "p:nth-child(1)", text)]
 //!     name: String,
 //!
-//!     #[html(selector = "p:nth-child(2)", attr = "inner")]
+//!     #[html(selector = "p:nth-child(2)", text)]
 //!     age: u8,
 //!
-//!     #[html(selector = "p:nth-child(3)", attr = "inner")]
+//!     #[html(selector = "p:nth-child(3)", text)]
 //!     like_lemon: bool,
 //! }
 //!
@@ -152,12 +242,12 @@
 //!
 //! #[derive(FromHtml)]
 //! struct SingleUser {
-//!     #[html(selector = "<>", attr = "inner")]
+//!     #[html(selector = "<>", text)]
 //!     name: String,
 //! }
 //! ```
 //!
-//! if multi element is selected and field type is not `Vec`, the first will be chosen
+//! if multi element is selected and field type is not `Vec`, the first will be chosen, use `nth` or `last` to choose another
 //!
 //! ```rust,ignore
 //!
@@ -172,7 +262,7 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
@@ -202,7 +292,7 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
@@ -225,8 +315,8 @@
 //!
 //! ##### specification
 //!
-//! - `value` refer to `innerHtml`
-//! - any other `attr` refer to `html element attribute`
+//! - the value of the html element attribute, trimmed, even if it's named `inner`
+//! - the text of element is taken by sources like `text`, see [text, own_text, inner_html, outer_html and tag](#text-own_text-inner_html-outer_html-and-tag)
 //!
 //! ```rust,ignore
 //! #[macro_use]
@@ -240,7 +330,7 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
@@ -265,7 +355,7 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //!
 //!     source: String,
@@ -332,7 +422,7 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
@@ -370,311 +460,229 @@
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
 //! let link = Link::from_html(r#"<a>Github</a>"#).unwrap();
 //! ```
 //!
-//! ### Field Type
+//! #### optional
 //!
-//! ##### any type implemented FromHtml, without generics
+//! ##### target
+//!
+//! `field` of type `Option<T>`
+//!
+//! ##### specification
+//!
+//! - `None` only when nothing is selected or the `attr` is not found
+//! - any other error, like a text which cannot be parsed or a nested struct with missing fields, is returned
+//!
+//! `optional` cannot be used with `default`.
 //!
 //! ```rust,ignore
-//! // panic
 //! #[macro_use]
 //! extern crate unhtml_derive;
 //! extern crate unhtml;
 //! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! #[html(selector = "a")]
-//! struct Link {
-//!     // no default
-//!     #[html(attr = "href")]
-//!     href: &str,
+//! struct Product {
+//!     #[html(selector = ".price", text, optional)]
+//!     price: Option<u32>,
 //! }
+//!
+//! assert_eq!(None, Product::from_html("<p></p>").unwrap().price);
+//! assert_eq!(Some(12), Product::from_html(r#"<p class="price">12</p>"#).unwrap().price);
+//! assert!(Product::from_html(r#"<p class="price">12.50 EUR</p>"#).is_err());
 //! ```
 //!
+//! ##### default behavior
+//!
+//! a field of type `Option<T>` without `optional` is `None` on any error
+//!
+//!
+//! #### truthy and falsy
+//!
+//! ##### target
+//!
+//! `field` of type `bool` or `Option<bool>` with `attr` or a source like `text`
+//!
+//! ##### literal type
+//!
+//! `string`, both can be repeated
+//!
+//! ##### specification
+//!
+//! - text in `truthy` is `true`, text in `falsy` is `false`, compared case-insensitively after trimming
+//! - when only `truthy` is given, any other text is `false`, and vice versa
+//! - when both are given, any other text is an error
+//! - an `Option<bool>` is `None` instead of an error, like other `Option<T>` fields
+//!
 //! ```rust,ignore
-//! // panic
 //! #[macro_use]
 //! extern crate unhtml_derive;
 //! extern crate unhtml;
 //! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! struct Website {
-//!     // no default
-//!     #[html(attr = "href")]
-//!     hrefs: std::collections::LinkedList<String>,
+//! struct Settings {
+//!     #[html(selector = ".notify", text, truthy = "✓", falsy = "✗")]
+//!     notify: bool,
+//!
+//!     #[html(selector = ".theme", attr = "data-mode", truthy = "dark")]
+//!     dark: bool,
 //! }
+//!
+//! let settings = Settings::from_html(r#"<i class="notify">✓</i><i class="theme" data-mode="light"></i>"#).unwrap();
+//! assert!(settings.notify);
+//! assert!(!settings.dark);
 //! ```
 //!
+//! ##### default behavior
+//!
+//! `true`, `yes`, `y`, `on`, `1`, `✓`, `✔` are `true` and `false`, `no`, `n`, `off`, `0`, `✗`, `✘` are `false`, case-insensitively
+//!
+//!
+//! #### presence
+//!
+//! ##### target
+//!
+//! `field` of type `bool` with `attr`
+//!
+//! ##### specification
+//!
+//! `true` if the first selected element has the `attr`, `false` if it doesn't or nothing is selected.
+//!
+//! `presence` cannot be used with `truthy` or `falsy`.
+//!
 //! ```rust,ignore
-//! // panic
 //! #[macro_use]
 //! extern crate unhtml_derive;
 //! extern crate unhtml;
 //! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! struct Website {
-//!     // no default
-//!     #[html(attr = "href")]
-//!     hrefs: [String],
+//! struct Form {
+//!     #[html(selector = "input[name=remember]", attr = "checked", presence)]
+//!     remember: bool,
 //! }
-//! ```
-//!
-//! ##### Vec
-//!
-//! > Should `use unhtml::VecFromHtml`
 //!
-//! ```rust,ignore
-//! extern crate unhtml_derive;
-//! extern crate unhtml;
-//! use unhtml::{self, FromHtml, VecFromHtml};
+//! assert!(Form::from_html(r#"<input name="remember" type="checkbox" checked>"#).unwrap().remember);
+//! assert!(!Form::from_html(r#"<input name="remember" type="checkbox">"#).unwrap().remember);
+//! ```
 //!
-//! #[derive(FromHtml)]
-//! struct TestUser {
-//!     #[html(selector = "p:nth-child(1)", attr = "inner")]
-//!     name: String,
 //!
-//!     #[html(selector = "p:nth-child(2)", attr = "inner")]
-//!     age: u8,
 //!
-//!     #[html(selector = "p:nth-child(3)", attr = "inner")]
-//!     like_lemon: bool,
-//! }
+//! #### key and value
 //!
-//! #[derive(FromHtml)]
-//! #[html(selector = "#test")]
-//! struct TestUsers {
-//!     #[html(selector = "div")]
-//!     users: Vec<TestUser>,
-//! }
+//! ##### target
 //!
-//! let users = TestUsers::from_html(r#"<!DOCTYPE html>
-//! <html lang="en">
-//! <head>
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//! </head>
-//! <body>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!         <div>
-//!             <p>BigBrother</p>
-//!             <p>21</p>
-//!             <p>false</p>
-//!         </div>
-//!     </div>
-//! </body>
-//! </html>"#).unwrap();
-//! let hexilee = &users.users[0];
-//! let big_brother = &users.users[1];
-//! assert_eq!("Hexilee", &hexilee.name);
-//! assert_eq!(20, hexilee.age);
-//! assert!(hexilee.like_lemon);
-//! assert_eq!("BigBrother", &big_brother.name);
-//! assert_eq!(21, big_brother.age);
-//! assert!(!big_brother.like_lemon);
-//! ```
+//! `field` of a map type like `HashMap<K, V>` or `BTreeMap<K, V>`
 //!
-//! as the documentation of crate `unhtml`, if you want `Vec<TestUser>` straightly, you can just:
+//! ##### specification
 //!
+//! - `key(selector = "...", attr = "...")` and `value(selector = "...", attr = "...")` must be used together
+//! - each element selected by the field `selector` is a row, keys and values selected in a row are paired in document order
+//! - without `selector`, the row itself is the key or the value
+//! - `key` requires `attr` and `K: FromText`, `value` without `attr` extracts `V: FromHtml`
+//! - `duplicate = "first"` keeps the first value of a key, `duplicate = "last"` keeps the last one
 //!
 //! ```rust,ignore
+//! #[macro_use]
 //! extern crate unhtml_derive;
 //! extern crate unhtml;
-//! use unhtml::{self, VecFromHtml};
+//! use std::collections::HashMap;
+//! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! struct TestUser {
-//!     #[html(selector = "p:nth-child(1)", attr = "inner")]
-//!     name: String,
+//! struct Product {
+//!     #[html(selector = "dl", key(selector = "dt", text), value(selector = "dd", text))]
+//!     specs: HashMap<String, String>,
 //!
-//!     #[html(selector = "p:nth-child(2)", attr = "inner")]
-//!     age: u8,
-//!
-//!     #[html(selector = "p:nth-child(3)", attr = "inner")]
-//!     like_lemon: bool,
+//!     #[html(selector = "tr", key(selector = "th", text), value(selector = "td", text), duplicate = "last")]
+//!     prices: HashMap<String, u32>,
 //! }
 //!
-//! let users = Vec::<TestUser>::from_html("#test > div", r#"<!DOCTYPE html>
-//! <html lang="en">
-//! <head>
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//! </head>
-//! <body>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!         <div>
-//!             <p>BigBrother</p>
-//!             <p>21</p>
-//!             <p>false</p>
-//!         </div>
-//!     </div>
-//! </body>
-//! </html>"#).unwrap();
-//! let hexilee = &users[0];
-//! let big_brother = &users[1];
-//! assert_eq!("Hexilee", &hexilee.name);
-//! assert_eq!(20, hexilee.age);
-//! assert!(hexilee.like_lemon);
-//! assert_eq!("BigBrother", &big_brother.name);
-//! assert_eq!(21, big_brother.age);
-//! assert!(!big_brother.like_lemon);
+//! let product = Product::from_html(r#"
+//! <dl><dt>Color</dt><dd>Yellow</dd><dt>Weight</dt><dd>100g</dd></dl>
+//! <table>
+//!     <tr><th>S</th><td>10</td></tr>
+//!     <tr><th>S</th><td>12</td></tr>
+//! </table>
+//! "#).unwrap();
+//! assert_eq!("Yellow", &product.specs["Color"]);
+//! assert_eq!(12, product.prices["S"]);
 //! ```
 //!
-//! ### Source HTML
+//! ##### default behavior
 //!
-//! ##### with top selector
-//! all source html will be parsed as `fragment`. The top element is `html` and there is no `DOCTYPE`, `head` or `body`.
+//! a duplicate key is an error
 //!
-//! ```html
-//! <!DOCTYPE html>
-//! <html lang="en">
-//! <head>
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//! </head>
-//! <body>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!     </div>
-//! </body>
-//! </html>
-//! ```
 //!
-//! will be parsed as:
 //!
-//! ```html
-//! <html lang="en">
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!     </div>
-//! </html>
-//! ```
+//! #### text_mode
 //!
-//! and
+//! ##### target
 //!
-//! ```html
-//! <p>Hexilee</p>
-//! ```
+//! `field` with `text` or `own_text`
 //!
-//! will be parsed as:
+//! ##### literal type
 //!
-//! ```html
-//! <html>
-//!     <p>Hexilee</p>
-//! </html>
-//! ```
+//! `string`, one of `raw`, `trim`, `join`, `collapse`, `block`
 //!
-//! ```rust,ignore
+//! ##### specification
 //!
-//! // panic
+//! - `raw`: all text nodes as they are
+//! - `trim`: trim each text node and concatenate them
+//! - `join`: trim each text node and join non-empty ones with a space
+//! - `collapse`: collapse every run of whitespace, including `&nbsp;`, into a space
+//! - `block`: like `collapse`, but break lines at `<br>` and around block elements, and separate table cells with a space like `innerText` of browsers
 //!
+//! The field type must implement `FromSource`, like types parsed by `FromStr`, types derived `FromText` and containers of them.
+//!
+//! ```rust,ignore
+//! #[macro_use]
 //! extern crate unhtml_derive;
 //! extern crate unhtml;
 //! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! struct Document {
-//!     // no default
-//!     #[html(selector = "head")]
-//!     head: String,
+//! struct Article {
+//!     #[html(selector = "h1", text, text_mode = "join")]
+//!     title: String,
 //!
-//!     #[html(selector = "body")]
+//!     #[html(selector = "div", text, text_mode = "block")]
 //!     body: String,
 //! }
 //!
-//! let dicument = Document::from_html(r#"<!DOCTYPE html>
-//! <html lang="en">
-//! <head>
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//! </head>
-//! <body>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!     </div>
-//! </body>
-//! </html>"#).unwrap();
+//! let article = Article::from_html("<h1>Hello <b>World</b></h1><div><p>a<br>b</p><p>c</p></div>").unwrap();
+//! assert_eq!("Hello World", &article.title);
+//! assert_eq!("a\nb\nc", &article.body);
 //! ```
 //!
-//! ##### without top selector
+//! ##### default behavior
+//!
+//! `trim`, `<p>Hello <b>World</b></p>` is `HelloWorld`
 //!
-//! when derived struct doesn't have `top selector`, all source html will be parsed as `pure fragment`. There is no `DOCTYPE`, `html`, `head` or `body`.
 //!
-//! ```html
-//! <!DOCTYPE html>
-//! <html lang="en">
-//! <head>
-//!     <meta charset="UTF-8">
-//!     <title>Title</title>
-//! </head>
-//! <body>
-//!     <div id="test">
-//!         <div>
-//!             <p>Hexilee</p>
-//!             <p>20</p>
-//!             <p>true</p>
-//!         </div>
-//!     </div>
-//! </body>
-//! </html>
-//! ```
 //!
-//! will be parsed as:
+//! #### regex
 //!
-//! ```html
-//! <meta charset="UTF-8">
-//! <title>Title</title>
-//! <div id="test">
-//!    <div>
-//!        <p>Hexilee</p>
-//!        <p>20</p>
-//!        <p>true</p>
-//!    </div>
-//! </div>
-//! ```
+//! ##### target
 //!
-//! and
+//! `field` with `attr` or a source like `text`
 //!
-//! ```html
-//! <p>Hexilee</p>
-//! ```
+//! ##### literal type
 //!
-//! will be parsed as:
+//! `string`, a regular expression checked at compile time
 //!
-//! ```html
-//! <p>Hexilee</p>
-//! ```
+//! ##### specification
+//!
+//! Search the text of `attr` or the source by the regex, and parse the first capture group, or the whole match without groups, into the field.
+//! Use `regex_group = "..."` to take a named group instead. Text without any match is a `TextParseError`.
+//!
+//! The field type must implement `FromSource`, like types parsed by `FromStr`, types derived `FromText` and containers of them.
 //!
 //! ```rust,ignore
 //! #[macro_use]
@@ -683,19 +691,854 @@
 //! use unhtml::{self, FromHtml};
 //!
 //! #[derive(FromHtml)]
-//! #[html(selector = "a")]
-//! struct Link {
+//! struct Product {
+//!     #[html(selector = ".price", text, regex = r"([\d.]+)")]
+//!     price: f64,
+//!
+//!     #[html(selector = ".price", text, regex = r"(?P<currency>[A-Z]{3})", regex_group = "currency")]
+//!     currency: String,
+//!
+//!     #[html(selector = "a", attr = "href", regex = r"/item/(\d+)")]
+//!     id: u32,
+//! }
+//!
+//! let product = Product::from_html(r#"<span class="price">Price: 12.50 EUR</span><a href="/item/42">Tea</a>"#).unwrap();
+//! assert_eq!(12.5, product.price);
+//! assert_eq!("EUR", &product.currency);
+//! assert_eq!(42, product.id);
+//! ```
+//!
+//!
+//!
+//! #### with and parse_with
+//!
+//! ##### target
+//!
+//! `field`, `parse_with` requires an `attr` or a source like `text`
+//!
+//! ##### literal type
+//!
+//! `string`, a path to a function
+//!
+//! ##### specification
+//!
+//! - `with`: call `fn(unhtml::ElemIter) -> unhtml::Result<T>` with the selected elements instead of extracting the field by its type.
+//! - `parse_with`: call `fn(&str) -> Result<T, E>` where `E: Display` with the text of `attr` or the source in the first selected element, after `text_mode` and `regex`. Its error becomes a `TextParseError`.
+//!
+//! They parse types without `FromHtml` or `FromText`, including foreign types.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use std::time::Duration;
+//! use unhtml::{self, ElemIter, FromHtml};
+//!
+//! fn count(select: ElemIter) -> unhtml::Result<usize> {
+//!     Ok(select.count())
+//! }
+//!
+//! fn seconds(text: &str) -> Result<Duration, std::num::ParseIntError> {
+//!     Ok(Duration::from_secs(text.parse()?))
+//! }
+//!
+//! #[derive(FromHtml)]
+//! struct Album {
+//!     #[html(selector = "li", with = "count")]
+//!     tracks: usize,
+//!
+//!     #[html(selector = "span", text, regex = r"(\d+) s", parse_with = "seconds")]
+//!     length: Duration,
+//! }
+//!
+//! let album = Album::from_html("<ul><li>Intro</li><li>Outro</li></ul><span>2467 s</span>").unwrap();
+//! assert_eq!(2, album.tracks);
+//! assert_eq!(Duration::from_secs(2467), album.length);
+//! ```
+//!
+//!
+//!
+//! #### text, own_text, inner_html, outer_html and tag
+//!
+//! ##### target
+//!
+//! `field`, cannot be used with `attr`
+//!
+//! ##### specification
+//!
+//! - `text`: text of all descendant text nodes, joined by `text_mode`
+//! - `own_text`: text of the direct child text nodes only, joined by `text_mode`
+//! - `inner_html`: html of the children
+//! - `outer_html`: html of the whole element
+//! - `tag`: name of the element, like `div`
+//!
+//! Like `attr`, they work with `regex` and `parse_with`, and `Vec` fields take them from every selected element.
+//! All of them except `text` require `FromSource` of the field type.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Label {
+//!     #[html(text)]
+//!     text: String,
+//!
+//!     #[html(own_text, text_mode = "join")]
+//!     own_text: String,
+//!
+//!     #[html(inner_html)]
+//!     inner_html: String,
+//!
+//!     #[html(selector = "*", tag)]
+//!     tags: Vec<String>,
+//! }
+//!
+//! let label = Label::from_html(r#"<label>Name <span>New</span> required</label>"#).unwrap();
+//! assert_eq!("NameNewrequired", &label.text);
+//! assert_eq!("Name required", &label.own_text);
+//! assert_eq!("Name <span>New</span> required", &label.inner_html);
+//! assert_eq!(vec!["label", "span"], label.tags);
+//! ```
+//!
+//! ##### default behavior
+//!
+//! `text_mode = "trim"` for `text` and `own_text`
+//!
+//!
+//!
+//! #### xpath
+//!
+//! ##### target
+//!
+//! `derive target`, `enum variant` or `field`, cannot be used with `selector`
+//!
+//! ##### literal type
+//!
+//! `string`, an XPath 1.0 expression selecting nodes, checked at compile time
+//!
+//! ##### specification
+//!
+//! Select elements by xpath instead of css-selector, relative to each element selected by the parent.
+//! Paths starting with `/` or `//` start from the document root. Text and attribute nodes selected by the xpath are skipped,
+//! use `text` or `attr` on the selected elements to extract them. Variables and namespaces are not supported.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! #[html(xpath = "//dl")]
+//! struct Product {
+//!     #[html(xpath = "dt[text()='Price']/following-sibling::dd[1]", text)]
+//!     price: u32,
+//!
+//!     #[html(xpath = "dd[not(@hidden)]", text)]
+//!     details: Vec<String>,
+//! }
+//!
+//! let product = Product::from_html("<dl><dt>Color</dt><dd>Yellow</dd><dt>Price</dt><dd>12</dd><dd hidden>-</dd></dl>").unwrap();
+//! assert_eq!(12, product.price);
+//! assert_eq!(vec!["Yellow", "12"], product.details);
+//! ```
+//!
+//! ##### default behavior
+//!
+//! same as `selector`
+//!
+//!
+//!
+//!
+//! #### nth, last, skip, take and range
+//!
+//! ##### target
+//!
+//! `derive target`, `enum variant` or `field`
+//!
+//! ##### literal type
+//!
+//! - `nth`, `skip` and `take`: non-negative `integer`
+//! - `last`: no value
+//! - `range`: `string` like `1..`, `..3` or `1..-1`
+//!
+//! ##### specification
+//!
+//! Take some of the selected elements in document order, even if they have different parents, which `:nth-child` cannot express.
+//!
+//! - `nth = N`: the element at zero-based index `N`, like `Iterator::nth`
+//! - `last`: the last element
+//! - `skip = N` and `take = N`: skip the first `N` elements, and take at most `N` elements after that
+//! - `range = "start..end"`: elements in the range, negative bounds count from the end like `-1` for the last one
+//!
+//! Only one of `nth`, `last`, `range` or `skip` and `take` can be used. Nothing left is the same as nothing selected.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Page {
+//!     #[html(selector = "li", text, nth = 1)]
+//!     first_item: String,
+//!
+//!     #[html(selector = "li", text, range = "1..-1")]
+//!     items: Vec<String>,
+//!
+//!     #[html(selector = "li", text, last)]
+//!     ad: String,
+//! }
+//!
+//! let page = Page::from_html("<ul><li>Header</li><li>Tea</li><li>Coffee</li><li>Ad</li></ul>").unwrap();
+//! assert_eq!("Tea", &page.first_item);
+//! assert_eq!(vec!["Tea", "Coffee"], page.items);
+//! assert_eq!("Ad", &page.ad);
+//! ```
+//!
+//! ##### default behavior
+//!
+//! all selected elements
+//!
+//!
+//!
+//!
+//! #### stream
+//!
+//! ##### target
+//!
+//! `derive target` with a `selector` of items, without lifetime
+//!
+//! ##### specification
+//!
+//! Implement `unhtml::FromHtmlStream` to extract items from a large document one at a time, without building the whole document.
+//! `T::stream()` takes html in chunks and `T::stream_reader(reader)` reads utf-8 html, both are iterators of items,
+//! and an item is ready as soon as its element is closed.
+//! Invalid utf-8 stops `stream_reader` with an `Io` error, and the items still open are closed and yielded after it.
+//!
+//! The selector of items is matched when an element starts, so it can only use tags, ids, classes, attributes and ` ` or `>` combinators.
+//! Selectors with `+`, `~` or pseudo-classes cause a compile-time panic. Fields of an item are selected in its element like `FromHtml`.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtmlStream};
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "#logs > li", stream)]
+//! struct Log {
+//!     #[html(selector = ".level", text)]
+//!     level: String,
+//!
+//!     #[html(attr = "data-line")]
+//!     line: u32,
+//! }
+//!
+//! let html = r#"<ul id="logs"><li data-line="1"><b class="level">INFO</b></li><li data-line="2"><b class="level">WARN</b></li></ul>"#;
+//! let logs: Vec<Log> = Log::stream_reader(html.as_bytes()).map(Result::unwrap).collect();
+//! assert_eq!("WARN", &logs[1].level);
+//! assert_eq!(2, logs[1].line);
+//! ```
+//!
+//!
+//!
+//!
+//! ### Field Type
+//!
+//! ##### any type implemented FromHtml, without generics
+//!
+//! ```rust,ignore
+//! // panic
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "a")]
+//! struct Link {
+//!     // no default
+//!     #[html(attr = "href")]
+//!     href: &str,
+//! }
+//! ```
+//!
+//! ```rust,ignore
+//! // panic
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Website {
+//!     // no default
+//!     #[html(attr = "href")]
+//!     hrefs: [String],
+//! }
+//! ```
+//!
+//! ##### Vec
+//!
+//! > Should `use unhtml::VecFromHtml`
+//!
+//! ```rust,ignore
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml, VecFromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct TestUser {
+//!     #[html(selector = "p:nth-child(1)", text)]
+//!     name: String,
+//!
+//!     #[html(selector = "p:nth-child(2)", text)]
+//!     age: u8,
+//!
+//!     #[html(selector = "p:nth-child(3)", text)]
+//!     like_lemon: bool,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "#test")]
+//! struct TestUsers {
+//!     #[html(selector = "div")]
+//!     users: Vec<TestUser>,
+//! }
+//!
+//! let users = TestUsers::from_html(r#"<!DOCTYPE html>
+//! <html lang="en">
+//! <head>
+//!     <meta charset="UTF-8">
+//!     <title>Title</title>
+//! </head>
+//! <body>
+//!     <div id="test">
+//!         <div>
+//!             <p>Hexilee</p>
+//!             <p>20</p>
+//!             <p>true</p>
+//!         </div>
+//!         <div>
+//!             <p>BigBrother</p>
+//!             <p>21</p>
+//!             <p>false</p>
+//!         </div>
+//!     </div>
+//! </body>
+//! </html>"#).unwrap();
+//! let hexilee = &users.users[0];
+//! let big_brother = &users.users[1];
+//! assert_eq!("Hexilee", &hexilee.name);
+//! assert_eq!(20, hexilee.age);
+//! assert!(hexilee.like_lemon);
+//! assert_eq!("BigBrother", &big_brother.name);
+//! assert_eq!(21, big_brother.age);
+//! assert!(!big_brother.like_lemon);
+//! ```
+//!
+//! as the documentation of crate `unhtml`, if you want `Vec<TestUser>` straightly, you can just:
+//!
+//!
+//! ```rust,ignore
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, VecFromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct TestUser {
+//!     #[html(selector = "p:nth-child(1)", text)]
+//!     name: String,
+//!
+//!     #[html(selector = "p:nth-child(2)", text)]
+//!     age: u8,
+//!
+//!     #[html(selector = "p:nth-child(3)", text)]
+//!     like_lemon: bool,
+//! }
+//!
+//! let users = Vec::<TestUser>::from_html("#test > div", r#"<!DOCTYPE html>
+//! <html lang="en">
+//! <head>
+//!     <meta charset="UTF-8">
+//!     <title>Title</title>
+//! </head>
+//! <body>
+//!     <div id="test">
+//!         <div>
+//!             <p>Hexilee</p>
+//!             <p>20</p>
+//!             <p>true</p>
+//!         </div>
+//!         <div>
+//!             <p>BigBrother</p>
+//!             <p>21</p>
+//!             <p>false</p>
+//!         </div>
+//!     </div>
+//! </body>
+//! </html>"#).unwrap();
+//! let hexilee = &users[0];
+//! let big_brother = &users[1];
+//! assert_eq!("Hexilee", &hexilee.name);
+//! assert_eq!(20, hexilee.age);
+//! assert!(hexilee.like_lemon);
+//! assert_eq!("BigBrother", &big_brother.name);
+//! assert_eq!(21, big_brother.age);
+//! assert!(!big_brother.like_lemon);
+//! ```
+//!
+//! ##### containers
+//!
+//! Like `Vec<T>`, `VecDeque<T>`, `LinkedList<T>`, `HashSet<T>` and `BTreeSet<T>` are built from all selected elements,
+//! and `[T; N]` fails unless exactly `N` elements are selected.
+//! `Box<T>`, `Rc<T>` and `Arc<T>` wrap `T`, so a struct can contain itself.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use std::collections::BTreeSet;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Menu {
+//!     #[html(selector = "span", text)]
+//!     title: String,
+//!
+//!     #[html(selector = ".tag", text)]
+//!     tags: BTreeSet<String>,
+//!
+//!     #[html(selector = ".menu")]
+//!     submenu: Option<Box<Menu>>,
+//! }
+//!
+//! let menu = Menu::from_html(r#"
+//! <span>A</span><i class="tag">x</i><i class="tag">x</i>
+//! <div class="menu"><span>B</span></div>
+//! "#).unwrap();
+//! assert_eq!(1, menu.tags.len());
+//! assert_eq!("B", &menu.submenu.unwrap().title);
+//! ```
+//!
+//!
+//! ##### Lenient
+//!
+//! `Lenient<T>` is built from all selected elements like `Vec<T>`, but an item failing to extract is skipped instead of failing the whole field.
+//! The index in the selected elements and the error of each skipped item are kept.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml, Lenient};
+//!
+//! #[derive(FromHtml)]
+//! struct Product {
+//!     #[html(selector = ".price", text)]
+//!     price: f64,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! struct Page {
+//!     #[html(selector = ".product")]
+//!     products: Lenient<Product>,
+//! }
+//!
+//! let page = Page::from_html(r#"
+//! <div class="product"><span class="price">1.5</span></div>
+//! <div class="product"><span class="price">free</span></div>
+//! <div class="product"><span class="price">0.9</span></div>
+//! "#).unwrap();
+//! assert_eq!(2, page.products.len());
+//! assert_eq!(0.9, page.products[1].price);
+//! for (index, err) in page.products.skipped() {
+//!     println!("product {} is skipped: {}", index, err);
+//! }
+//! assert_eq!(vec![1], page.products.skipped_indices().collect::<Vec<_>>());
+//! ```
+//!
+//!
+//! ##### Url
+//!
+//! With the `url` feature, `unhtml::url::Url` fields are resolved against the base url given to `from_html_with_base`, `from_document_with_base`,
+//! `from_element_with_base` or `iter_html_with_base`, and their `FromHtmlRef` variants like `from_document_ref_with_base`.
+//! The `href` of the first `<base>` element in the document is honoured, it's resolved against the base url first.
+//! Other methods like `from_html` only take an absolute `<base>` or absolute urls, a relative or invalid url fails with `Error::TextParseError`.
+//!
+//! The base url is passed down to every field in an `unhtml::Extraction`, types implementing `FromHtml` by hand
+//! forward it to their fields by `from_elements_with`, `from_inner_text_with` and the like.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, url::Url, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Page {
+//!     #[html(selector = "a", attr = "href")]
+//!     links: Vec<Url>,
+//!
+//!     #[html(selector = "img", attr = "src")]
+//!     logo: Url,
+//! }
+//!
+//! let html = r#"<base href="/static/"><img src="logo.png"><a href="/about">About</a><a href="https://github.com">Github</a>"#;
+//! let base = Url::parse("https://example.com/blog/").unwrap();
+//! let page = Page::from_html_with_base(html, &base).unwrap();
+//! assert_eq!("https://example.com/static/logo.png", page.logo.as_str());
+//! assert_eq!("https://example.com/about", page.links[0].as_str());
+//! assert_eq!("https://github.com/", page.links[1].as_str());
+//! assert!(Page::from_html(html).is_err());
+//! ```
+//!
+//!
+//! ##### raw html
+//!
+//! A field without `attr` of type `String` or `OuterHtml` keeps html of the whole element, `InnerHtml` keeps html of its children,
+//! `Fragment` parses the element again so it can be extracted later. A struct with a lifetime parameter can also keep the `ElementRef<'a>`.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, Fragment, FromHtml, InnerHtml};
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "p")]
+//! struct Paragraph {
+//!     inner: InnerHtml,
+//!     fragment: Fragment,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "a")]
+//! struct Link {
+//!     #[html(attr = "href")]
+//!     href: String,
+//! }
+//!
+//! fn main() {
+//!     let paragraph = Paragraph::from_html(r#"<p>Hello <a href="https://github.com">Github</a></p>"#).unwrap();
+//!     assert_eq!(r#"Hello <a href="https://github.com">Github</a>"#, &*paragraph.inner);
+//!     let link: Link = paragraph.fragment.extract().unwrap();
+//!     assert_eq!("https://github.com", &link.href);
+//! }
+//! ```
+//!
+//!
+//! ##### borrowed fields
+//!
+//! A struct or enum with a lifetime parameter can borrow `&'a str` or `Cow<'a, str>` from the document.
+//! It implements only `FromHtmlRef<'a>`, extract it by `from_document_ref` with a parsed `Html`.
+//! `&str` cannot hold text split into multiple nodes, use `Cow<str>` for inner text of such element.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use std::borrow::Cow;
+//! use unhtml::{scraper::Html, FromHtmlRef};
+//!
+//! #[derive(FromHtml)]
+//! #[html(selector = "a")]
+//! struct Link<'a> {
+//!     #[html(attr = "href")]
+//!     href: &'a str,
+//!
+//!     #[html(text)]
+//!     text: Cow<'a, str>,
+//! }
+//!
+//! fn main() {
+//!     let document = Html::parse_document(r#"<a href="https://github.com">Git<b>hub</b></a>"#);
+//!     let link = Link::from_document_ref(&document).unwrap();
+//!     assert_eq!("https://github.com", link.href);
+//!     assert_eq!("Github", link.text);
+//! }
+//! ```
+//!
+//!
+//! ### FromText
+//!
+//! `#[derive(FromText)]` on a `struct` uses its `FromStr` implementation, it also implements `FromSource` for fields with `text_mode`, `regex` or sources like `own_text`.
+//!
+//! On an `enum` without fields, text is matched with names of variants:
+//!
+//! - `rename = "..."` replaces the name of a variant
+//! - `alias = "..."` adds another name, it can be used for several times
+//! - `case_insensitive` on the `enum` ignores case when matching
+//!
+//! Each name can only be used by one variant, after lowercasing with `case_insensitive`.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromText, Debug, Eq, PartialEq)]
+//! #[html(case_insensitive)]
+//! enum Availability {
+//!     #[html(rename = "In stock", alias = "available")]
+//!     InStock,
+//!     SoldOut,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! struct Product {
+//!     #[html(selector = ".availability", text)]
+//!     availability: Availability,
+//! }
+//!
+//! let product = Product::from_html(r#"<p class="availability">IN STOCK</p>"#).unwrap();
+//! assert_eq!(Availability::InStock, product.availability);
+//! ```
+//!
+//!
+//! ### FromRow
+//!
+//! `#[derive(FromRow)]` maps a row of `Table<T>` by column header instead of `td:nth-child(n)` selectors.
+//! `column = "..."` selects the cell under that header, other attributes work like `FromHtml` fields in the cell.
+//! A field without `column` is extracted from the `tr` element.
+//!
+//! `Table<T>` takes the first selected `table`, or the first `table` in the selected element:
+//!
+//! - header rows are the rows in `thead`, or the leading rows made of only `th` without `thead`
+//! - the header of a column is the text of its header cells from top to bottom, joined by a space
+//! - cells with `colspan` or `rowspan` are repeated in every column and row they span
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml, Table};
+//!
+//! #[derive(FromRow)]
+//! struct Fruit {
+//!     #[html(column = "Name", text)]
+//!     name: String,
+//!
+//!     #[html(column = "Price Max", text)]
+//!     max_price: u32,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! struct Shop {
+//!     #[html(selector = "#fruits")]
+//!     fruits: Table<Fruit>,
+//! }
+//!
+//! let shop = Shop::from_html(r#"
+//! <table id="fruits">
+//!     <thead>
+//!         <tr><th rowspan="2">Name</th><th colspan="2">Price</th></tr>
+//!         <tr><th>Min</th><th>Max</th></tr>
+//!     </thead>
+//!     <tbody>
+//!         <tr><th>Lemon</th><td>1</td><td>2</td></tr>
+//!     </tbody>
+//! </table>
+//! "#).unwrap();
+//! assert_eq!("Lemon", &shop.fruits[0].name);
+//! assert_eq!(2, shop.fruits[0].max_price);
+//! ```
+//!
+//!
+//! ### Items
+//!
+//! `T::iter_html(&document, &selector)` extracts `T` from each selected element lazily, like the items of `Vec<T>` one at a time,
+//! so the caller can stop early or process items without holding all of them. `T::iter_html_ref` borrows items from the document.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, scraper::{Html, Selector}, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Item {
+//!     #[html(attr = "data-id")]
+//!     id: u32,
+//! }
+//!
+//! let document = Html::parse_document(r#"<ul><li data-id="3"></li><li data-id="2"></li><li data-id="1"></li></ul>"#);
+//! let selector = Selector::parse("li").unwrap();
+//! let newer: Vec<u32> = Item::iter_html(&document, &selector)
+//!     .map(|item| item.unwrap().id)
+//!     .take_while(|id| *id > 2)
+//!     .collect();
+//! assert_eq!(vec![3], newer);
+//! ```
+//!
+//!
+//!
+//! ### Deserialize
+//!
+//! With feature `serde`, `unhtml::de` extracts any `#[derive(Deserialize)]` type instead of `#[derive(FromHtml)]`.
+//! A field is looked up by its name after `#[serde(rename = "...")]`, or by a key given to `unhtml::de::FieldMap` aside from the type:
+//!
+//! - `selector`: elements selected by the css selector
+//! - `selector@attr`: the attribute of elements selected by the selector
+//! - `@attr`: the attribute of the current elements
+//! - `.`: the current elements
+//!
+//! Scalars are parsed from the trimmed text or attribute of the first element, `Vec` takes every element,
+//! `Option` is `None` if nothing is selected or the attribute is missing, and unit enum variants are matched by the text.
+//!
+//! ```rust,ignore
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Product {
+//!     #[serde(rename = "h2")]
+//!     name: String,
+//!
+//!     #[serde(rename = ".price@data-value")]
+//!     price: f64,
+//! }
+//!
+//! #[derive(Deserialize)]
+//! struct Shop {
+//!     title: String,
+//!
+//!     #[serde(rename = ".product")]
+//!     products: Vec<Product>,
+//! }
+//!
+//! let shop: Shop = unhtml::de::from_html(r#"
+//! <title>Shop</title>
+//! <div class="product"><h2>Lemon tea</h2><span class="price" data-value="1.5">$1.50</span></div>
+//! "#).unwrap();
+//! assert_eq!("Shop", &shop.title);
+//! assert_eq!(1.5, shop.products[0].price);
+//! ```
+//!
+//!
+//!
+//! ### Source HTML
+//!
+//! ##### document
+//!
+//! `from_html` parses source html as a document, so `html`, `head` and `body` are implied when they're missing.
+//!
+//! ```html,ignore
+//! <title>Title</title>
+//! <p>Hexilee</p>
+//! ```
+//!
+//! will be parsed as:
+//!
+//! ```html,ignore
+//! <html>
+//!     <head>
+//!         <title>Title</title>
+//!     </head>
+//!     <body>
+//!         <p>Hexilee</p>
+//!     </body>
+//! </html>
+//! ```
+//!
+//! Fields and the top selector select from the root `html` element, and a field without selector takes the `html` element itself.
+//!
+//! ##### fragment
+//!
+//! `from_fragment` parses source html as a fragment in `body`, the root element is `html` and there is no `head` or `body`.
+//!
+//! ```html,ignore
+//! <html>
+//!     <title>Title</title>
+//!     <p>Hexilee</p>
+//! </html>
+//! ```
+//!
+//! Some elements are dropped out of their context, like `<tr>` and `<td>` out of a table,
+//! use `from_html_in_context` to parse a fragment in another context element.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Row {
+//!     #[html(selector = "td", text)]
+//!     cells: Vec<String>,
+//! }
+//!
+//! let html = "<tr><td>Hexilee</td><td>20</td></tr>";
+//! assert!(Row::from_fragment(html).unwrap().cells.is_empty());
+//! assert_eq!(vec!["Hexilee", "20"], Row::from_html_in_context(html, "tbody").unwrap().cells);
+//! ```
+//!
+//! ##### parsed document and elements
+//!
+//! `from_document` extracts from a parsed document or fragment, so a page can be parsed once for many types.
+//! `from_element` extracts from an element as the root element, selectors match its descendants.
+//! `from_document_ref` and `from_element_ref` are the same for types borrowing from the document.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, scraper::{Html, Selector}, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Title {
+//!     #[html(selector = "title", text)]
+//!     value: String,
+//! }
+//!
+//! #[derive(FromHtml)]
+//! struct Link {
 //!     #[html(attr = "href")]
 //!     href: String,
 //!
-//!     #[html(attr = "inner")]
+//!     #[html(text)]
 //!     value: String,
 //! }
 //!
-//! let link = Link::from_html(r#"<a href="https://github.com">Github</a>"#).unwrap();
+//! let document = Html::parse_document(r#"<title>Links</title><a href="https://github.com">Github</a>"#);
+//! assert_eq!("Links", &Title::from_document(&document).unwrap().value);
+//!
+//! let selector = Selector::parse("a").unwrap();
+//! let link = Link::from_element(document.select(&selector).next().unwrap()).unwrap();
 //! assert_eq!("https://github.com", &link.href);
 //! assert_eq!("Github", &link.value);
 //! ```
+//!
+//! ##### bytes
+//!
+//! `from_bytes` and `from_reader` take html in any charset, decoded by the first charset found in the byte order mark,
+//! `<meta charset="...">` or `<meta http-equiv="Content-Type" content="...">` in the first 1024 bytes.
+//! Without any of them, bytes are decoded as UTF-8 if they're valid, or Windows-1252 otherwise.
+//! `from_bytes_with_charset` takes a charset hint from the transport layer, like the `Content-Type` header, which takes precedence over `<meta>`.
+//!
+//! ```rust,ignore
+//! #[macro_use]
+//! extern crate unhtml_derive;
+//! extern crate unhtml;
+//! use unhtml::{self, FromHtml};
+//!
+//! #[derive(FromHtml)]
+//! struct Page {
+//!     #[html(selector = "p", text)]
+//!     text: String,
+//! }
+//!
+//! let page = Page::from_bytes(b"<meta charset=\"windows-1252\"><p>caf\xe9</p>").unwrap();
+//! assert_eq!("café", &page.text);
+//! let page = Page::from_bytes_with_charset(b"<p>caf\xe9</p>", "text/html; charset=iso-8859-1").unwrap();
+//! assert_eq!("café", &page.text);
+//! ```
 
 extern crate proc_macro;
 
@@ -712,7 +1555,14 @@ pub fn html_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
-#[proc_macro_derive(FromText)]
+#[proc_macro_derive(FromRow, attributes(html))]
+pub fn row_derive(input: TokenStream) -> TokenStream {
+    html::derive_row(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(FromText, attributes(html))]
 pub fn text_derive(input: TokenStream) -> TokenStream {
     text::derive(input)
         .unwrap_or_else(|err| err.to_compile_error())
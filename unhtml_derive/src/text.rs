@@ -1,12 +1,31 @@
-use proc_macro2::TokenStream;
+use super::attr_meta::AttrMeta;
+use super::html::ref_generics;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use std::collections::HashMap;
+use std::convert::TryInto;
 use syn::parse_macro_input::parse;
-use syn::{ItemStruct, Result};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, Ident, Result};
 
 pub fn derive(input: proc_macro::TokenStream) -> Result<TokenStream> {
-    let target = parse::<ItemStruct>(input)?;
+    let target = parse::<DeriveInput>(input)?;
     let (impl_generics, ty_generics, where_clause) = target.generics.split_for_impl();
     let struct_name = target.ident.clone();
+    // text is parsed in `from_inner_text`, `from_attr` and `from_source`
+    let from_text = |text: TokenStream| -> Result<TokenStream> {
+        match &target.data {
+            Data::Enum(data) => {
+                let attr_meta: AttrMeta = target.attrs.clone().try_into()?;
+                gen_match_variant(&struct_name, data, attr_meta.case_insensitive, text)
+            }
+            _ => Ok(gen_from_str(&struct_name, text)),
+        }
+    };
+    let from_inner_text = from_text(quote!(&ret))?;
+    let from_attr = from_text(quote!(attr.trim()))?;
+    let from_source = from_text(quote!(&text))?;
+    let ref_generics = ref_generics(&target.generics);
+    let (ref_impl_generics, _, _) = ref_generics.split_for_impl();
     Ok(quote!(
         impl #impl_generics unhtml::FromText for #struct_name #ty_generics #where_clause {
            fn from_inner_text(select: unhtml::ElemIter) -> unhtml::Result<Self> {
@@ -15,13 +34,105 @@ pub fn derive(input: proc_macro::TokenStream) -> Result<TokenStream> {
                 for next_segment in first.text() {
                     ret += next_segment.trim();
                 }
-                Self::from_str(&ret).map_err(|err| (ret.to_owned(), stringify!(#struct_name).to_owned(), err.to_string()).into())
+                #from_inner_text
             }
             fn from_attr(select: unhtml::ElemIter, attr: &str) -> unhtml::Result<Self> {
                 let first = select.next().ok_or(())?;
                 let attr = first.value().attr(attr).ok_or((attr.to_owned(), first.html()))?;
-                Self::from_str(attr.trim()).map_err(|err| (attr.trim().to_owned(), stringify!(#struct_name).to_owned(), err.to_string()).into())
+                #from_attr
+            }
+        }
+
+        impl #impl_generics unhtml::FromSource for #struct_name #ty_generics #where_clause {
+            fn from_source(select: unhtml::ElemIter, source: &unhtml::Source) -> unhtml::Result<Self> {
+                let first = select.next().ok_or(())?;
+                let text = source.text(first)?;
+                #from_source
+            }
+        }
+
+        impl #ref_impl_generics unhtml::FromTextRef<'__unhtml> for #struct_name #ty_generics #where_clause {
+            fn from_inner_text_ref(select: unhtml::ElemIter<'_, '__unhtml>) -> unhtml::Result<Self> {
+                <Self as unhtml::FromText>::from_inner_text(select)
+            }
+            fn from_attr_ref(select: unhtml::ElemIter<'_, '__unhtml>, attr: &str) -> unhtml::Result<Self> {
+                <Self as unhtml::FromText>::from_attr(select, attr)
+            }
+        }
+
+        impl #ref_impl_generics unhtml::FromSourceRef<'__unhtml> for #struct_name #ty_generics #where_clause {
+            fn from_source_ref(select: unhtml::ElemIter<'_, '__unhtml>, source: &unhtml::Source) -> unhtml::Result<Self> {
+                <Self as unhtml::FromSource>::from_source(select, source)
             }
         }
     ))
 }
+
+fn gen_from_str(struct_name: &Ident, text: TokenStream) -> TokenStream {
+    quote!({
+        let text: &str = #text;
+        Self::from_str(text).map_err(|err| (text.to_owned(), stringify!(#struct_name).to_owned(), err.to_string()).into())
+    })
+}
+
+/// match `text` with names of variants, `rename` replaces the name and `alias` adds more names
+fn gen_match_variant(
+    enum_name: &Ident,
+    data: &DataEnum,
+    case_insensitive: bool,
+    text: TokenStream,
+) -> Result<TokenStream> {
+    let mut arms = quote!();
+    let mut expected = Vec::new();
+    let mut seen = HashMap::new();
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        if let Fields::Named(_) | Fields::Unnamed(_) = variant.fields {
+            return Err(Error::new(
+                variant_ident.span(),
+                "FromText can only be derived for enum without fields",
+            ));
+        }
+        let meta: AttrMeta = variant.attrs.clone().try_into()?;
+        let mut names = vec![meta.rename.unwrap_or_else(|| variant_ident.to_string())];
+        names.extend(meta.aliases);
+        expected.extend(names.iter().map(|name| format!("`{}`", name)));
+        if case_insensitive {
+            names = names.iter().map(|name| name.to_lowercase()).collect();
+        }
+        // a duplicate name would always match the earlier variant
+        for name in &names {
+            if let Some(other) = seen.insert(name.clone(), variant_ident) {
+                let message = if other == variant_ident {
+                    format!("name `{}` is used twice by variant `{}`", name, other)
+                } else {
+                    format!(
+                        "name `{}` of variant `{}` is already used by variant `{}`",
+                        name, variant_ident, other
+                    )
+                };
+                return Err(Error::new(variant_ident.span(), message));
+            }
+        }
+        arms = quote!(#arms #(#names)|* => Ok(Self::#variant_ident),);
+    }
+    if expected.is_empty() {
+        return Err(Error::new(
+            Span::call_site(),
+            "FromText cannot be derived for enum without variants",
+        ));
+    }
+    let expected = format!("expected one of {}", expected.join(", "));
+    let key = if case_insensitive {
+        quote!(&*text.to_lowercase())
+    } else {
+        quote!(text)
+    };
+    Ok(quote!({
+        let text: &str = #text;
+        match #key {
+            #arms
+            _ => Err((text.to_owned(), stringify!(#enum_name).to_owned(), #expected.to_owned()).into()),
+        }
+    }))
+}
@@ -0,0 +1,540 @@
+use crate::parser::{ArithOp, Axis, CompareOp, Expr, Function, NodeTest, Step};
+use ego_tree::{NodeId, NodeRef};
+use scraper::Node;
+use std::collections::HashMap;
+use std::iter;
+
+/// a node of the xpath data model, attributes are not nodes of the scraper tree
+#[derive(Debug, Clone, Copy)]
+pub enum XNode<'a> {
+    Tree(NodeRef<'a, Node>),
+    Attr(NodeRef<'a, Node>, &'a str, &'a str),
+}
+
+impl<'a> PartialEq for XNode<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (XNode::Tree(a), XNode::Tree(b)) => a.id() == b.id(),
+            (XNode::Attr(a, a_name, _), XNode::Attr(b, b_name, _)) => {
+                a.id() == b.id() && a_name == b_name
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> XNode<'a> {
+    fn tree_node(self) -> NodeRef<'a, Node> {
+        match self {
+            XNode::Tree(node) | XNode::Attr(node, _, _) => node,
+        }
+    }
+
+    fn string_value(self) -> String {
+        match self {
+            XNode::Tree(node) => match node.value() {
+                Node::Text(text) => (**text).to_owned(),
+                Node::Comment(comment) => (**comment).to_owned(),
+                Node::Document | Node::Fragment | Node::Element(_) => node
+                    .descendants()
+                    .filter_map(|node| node.value().as_text())
+                    .map(|text| &**text)
+                    .collect(),
+                _ => String::new(),
+            },
+            XNode::Attr(_, _, value) => value.to_owned(),
+        }
+    }
+
+    fn name(self) -> &'a str {
+        match self {
+            XNode::Tree(node) => match node.value() {
+                Node::Element(element) => element.name(),
+                Node::ProcessingInstruction(pi) => &pi.target,
+                _ => "",
+            },
+            XNode::Attr(_, name, _) => name,
+        }
+    }
+}
+
+enum Value<'a> {
+    Nodes(Vec<XNode<'a>>),
+    Bool(bool),
+    Number(f64),
+    String(String),
+}
+
+impl<'a> Value<'a> {
+    fn boolean(&self) -> bool {
+        match self {
+            Value::Nodes(nodes) => !nodes.is_empty(),
+            Value::Bool(value) => *value,
+            Value::Number(number) => *number != 0.0 && !number.is_nan(),
+            Value::String(string) => !string.is_empty(),
+        }
+    }
+
+    fn number(&self) -> f64 {
+        match self {
+            Value::Nodes(_) => parse_number(&self.string()),
+            Value::Bool(value) => f64::from(u8::from(*value)),
+            Value::Number(number) => *number,
+            Value::String(string) => parse_number(string),
+        }
+    }
+
+    fn string(&self) -> String {
+        match self {
+            Value::Nodes(nodes) => nodes
+                .first()
+                .map_or_else(String::new, |node| node.string_value()),
+            Value::Bool(value) => value.to_string(),
+            Value::Number(number) => format_number(*number),
+            Value::String(string) => string.clone(),
+        }
+    }
+
+    fn into_nodes(self) -> Vec<XNode<'a>> {
+        match self {
+            Value::Nodes(nodes) => nodes,
+            // node-set expressions are checked by the parser
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn parse_number(text: &str) -> f64 {
+    let text = text.trim();
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let valid = !digits.is_empty()
+        && digits != "."
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.matches('.').count() <= 1;
+    if valid {
+        text.parse().unwrap_or(f64::NAN)
+    } else {
+        f64::NAN
+    }
+}
+
+fn format_number(number: f64) -> String {
+    if number.is_nan() {
+        "NaN".to_owned()
+    } else if number.is_infinite() {
+        if number > 0.0 {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }
+        .to_owned()
+    } else if number == number.trunc() {
+        // also prints `-0` as `0`
+        format!("{}", number.trunc() as i64)
+    } else {
+        number.to_string()
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Context<'a> {
+    node: XNode<'a>,
+    position: usize,
+    size: usize,
+}
+
+pub struct Evaluator<'a> {
+    root: NodeRef<'a, Node>,
+}
+
+impl<'a> Evaluator<'a> {
+    pub fn new(node: NodeRef<'a, Node>) -> Self {
+        Self {
+            root: node.tree().root(),
+        }
+    }
+
+    /// nodes selected by `expr` from `node`
+    pub fn select(&self, expr: &Expr, node: NodeRef<'a, Node>) -> Vec<NodeRef<'a, Node>> {
+        let context = Context {
+            node: XNode::Tree(node),
+            position: 1,
+            size: 1,
+        };
+        self.eval(expr, context)
+            .into_nodes()
+            .into_iter()
+            .filter_map(|node| match node {
+                XNode::Tree(node) => Some(node),
+                XNode::Attr(..) => None,
+            })
+            .collect()
+    }
+
+    fn eval(&self, expr: &Expr, context: Context<'a>) -> Value<'a> {
+        match expr {
+            Expr::Or(left, right) => Value::Bool(
+                self.eval(left, context).boolean() || self.eval(right, context).boolean(),
+            ),
+            Expr::And(left, right) => Value::Bool(
+                self.eval(left, context).boolean() && self.eval(right, context).boolean(),
+            ),
+            Expr::Compare(op, left, right) => Value::Bool(compare(
+                *op,
+                &self.eval(left, context),
+                &self.eval(right, context),
+            )),
+            Expr::Arith(op, left, right) => {
+                let (left, right) = (
+                    self.eval(left, context).number(),
+                    self.eval(right, context).number(),
+                );
+                Value::Number(match op {
+                    ArithOp::Add => left + right,
+                    ArithOp::Sub => left - right,
+                    ArithOp::Mul => left * right,
+                    ArithOp::Div => left / right,
+                    ArithOp::Mod => left % right,
+                })
+            }
+            Expr::Negate(expr) => Value::Number(-self.eval(expr, context).number()),
+            Expr::Union(left, right) => {
+                let mut nodes = self.eval(left, context).into_nodes();
+                nodes.extend(self.eval(right, context).into_nodes());
+                Value::Nodes(self.sorted(nodes))
+            }
+            Expr::Path { absolute, steps } => {
+                let start = if *absolute {
+                    XNode::Tree(self.root)
+                } else {
+                    context.node
+                };
+                Value::Nodes(self.steps(vec![start], steps))
+            }
+            Expr::Filter {
+                primary,
+                predicates,
+                steps,
+            } => {
+                let mut nodes = self.eval(primary, context).into_nodes();
+                for predicate in predicates {
+                    nodes = self.filter(nodes, predicate);
+                }
+                Value::Nodes(self.steps(nodes, steps))
+            }
+            Expr::Literal(literal) => Value::String(literal.clone()),
+            Expr::Number(number) => Value::Number(*number),
+            Expr::Call(function, args) => self.call(*function, args, context),
+        }
+    }
+
+    /// apply `steps` to `nodes` in document order
+    fn steps(&self, mut nodes: Vec<XNode<'a>>, steps: &[Step]) -> Vec<XNode<'a>> {
+        for step in steps {
+            let single = nodes.len() == 1;
+            let mut selected = Vec::new();
+            for node in nodes {
+                let mut candidates: Vec<_> = axis(step.axis, node)
+                    .filter(|candidate| matches(&step.test, step.axis, *candidate))
+                    .collect();
+                for predicate in &step.predicates {
+                    candidates = self.filter(candidates, predicate);
+                }
+                selected.extend(candidates);
+            }
+            nodes = match (single, step.axis.is_reverse()) {
+                // nodes of a single node on an axis are distinct
+                (true, false) => selected,
+                (true, true) => {
+                    selected.reverse();
+                    selected
+                }
+                _ => self.sorted(selected),
+            };
+        }
+        nodes
+    }
+
+    /// nodes satisfying `predicate`, the position is the index in `nodes`
+    fn filter(&self, nodes: Vec<XNode<'a>>, predicate: &Expr) -> Vec<XNode<'a>> {
+        let size = nodes.len();
+        nodes
+            .into_iter()
+            .enumerate()
+            .filter(|(index, node)| {
+                let context = Context {
+                    node: *node,
+                    position: index + 1,
+                    size,
+                };
+                match self.eval(predicate, context) {
+                    Value::Number(number) => number == (index + 1) as f64,
+                    value => value.boolean(),
+                }
+            })
+            .map(|(_, node)| node)
+            .collect()
+    }
+
+    /// distinct nodes in document order, compared by the sibling indices on their paths from the root
+    fn sorted(&self, mut nodes: Vec<XNode<'a>>) -> Vec<XNode<'a>> {
+        let mut indices = HashMap::<NodeId, usize>::new();
+        let mut path = |node: NodeRef<'a, Node>| {
+            let mut path: Vec<usize> = iter::once(node)
+                .chain(node.ancestors())
+                .filter_map(|node| {
+                    let parent = node.parent()?;
+                    if !indices.contains_key(&node.id()) {
+                        let children = parent.children().enumerate();
+                        indices.extend(children.map(|(index, child)| (child.id(), index)));
+                    }
+                    indices.get(&node.id()).copied()
+                })
+                .collect();
+            path.reverse();
+            path
+        };
+        // attributes come after their element and before its children
+        nodes.sort_by_cached_key(|node| {
+            let name = match node {
+                XNode::Tree(_) => None,
+                XNode::Attr(_, name, _) => Some(*name),
+            };
+            (path(node.tree_node()), name)
+        });
+        nodes.dedup();
+        nodes
+    }
+
+    fn call(&self, function: Function, args: &[Expr], context: Context<'a>) -> Value<'a> {
+        let string = |index: usize| match args.get(index) {
+            Some(arg) => self.eval(arg, context).string(),
+            None => context.node.string_value(),
+        };
+        let number = |index: usize| self.eval(&args[index], context).number();
+        let first_node = || match args.first() {
+            Some(arg) => self.eval(arg, context).into_nodes().into_iter().next(),
+            None => Some(context.node),
+        };
+        match function {
+            Function::Last => Value::Number(context.size as f64),
+            Function::Position => Value::Number(context.position as f64),
+            Function::Count => {
+                Value::Number(self.eval(&args[0], context).into_nodes().len() as f64)
+            }
+            Function::LocalName | Function::Name => {
+                Value::String(first_node().map_or("", XNode::name).to_owned())
+            }
+            Function::String => Value::String(string(0)),
+            Function::Concat => Value::String((0..args.len()).map(string).collect()),
+            Function::StartsWith => Value::Bool(string(0).starts_with(&string(1))),
+            Function::EndsWith => Value::Bool(string(0).ends_with(&string(1))),
+            Function::Contains => Value::Bool(string(0).contains(&string(1))),
+            Function::SubstringBefore => {
+                let (text, pattern) = (string(0), string(1));
+                Value::String(
+                    text.find(&pattern)
+                        .map_or("", |index| &text[..index])
+                        .to_owned(),
+                )
+            }
+            Function::SubstringAfter => {
+                let (text, pattern) = (string(0), string(1));
+                Value::String(
+                    text.find(&pattern)
+                        .map_or("", |index| &text[index + pattern.len()..])
+                        .to_owned(),
+                )
+            }
+            Function::Substring => {
+                let text = string(0);
+                let start = number(1).round();
+                let end = if args.len() > 2 {
+                    start + number(2).round()
+                } else {
+                    f64::INFINITY
+                };
+                // positions of characters start at 1
+                Value::String(
+                    text.chars()
+                        .enumerate()
+                        .filter(|(index, _)| {
+                            let position = (index + 1) as f64;
+                            position >= start && position < end
+                        })
+                        .map(|(_, c)| c)
+                        .collect(),
+                )
+            }
+            Function::StringLength => Value::Number(string(0).chars().count() as f64),
+            Function::NormalizeSpace => {
+                Value::String(string(0).split_whitespace().collect::<Vec<_>>().join(" "))
+            }
+            Function::Translate => {
+                let (text, from, to) = (string(0), string(1), string(2));
+                let to: Vec<char> = to.chars().collect();
+                Value::String(
+                    text.chars()
+                        .filter_map(|c| match from.chars().position(|from| from == c) {
+                            Some(index) => to.get(index).copied(),
+                            None => Some(c),
+                        })
+                        .collect(),
+                )
+            }
+            Function::Not => Value::Bool(!self.eval(&args[0], context).boolean()),
+            Function::True => Value::Bool(true),
+            Function::False => Value::Bool(false),
+            Function::Boolean => Value::Bool(self.eval(&args[0], context).boolean()),
+            Function::Number => Value::Number(match args.first() {
+                Some(arg) => self.eval(arg, context).number(),
+                None => parse_number(&context.node.string_value()),
+            }),
+            Function::Sum => Value::Number(
+                self.eval(&args[0], context)
+                    .into_nodes()
+                    .into_iter()
+                    .map(|node| parse_number(&node.string_value()))
+                    .sum(),
+            ),
+            Function::Floor => Value::Number(number(0).floor()),
+            Function::Ceiling => Value::Number(number(0).ceil()),
+            // rounds half up, unlike `f64::round`
+            Function::Round => Value::Number((number(0) + 0.5).floor()),
+        }
+    }
+}
+
+/// nodes on `axis` from `node`, in reverse document order for reverse axes
+fn axis<'a>(axis: Axis, node: XNode<'a>) -> Box<dyn Iterator<Item = XNode<'a>> + 'a> {
+    let tree = |iter: Box<dyn Iterator<Item = NodeRef<'a, Node>> + 'a>| -> Box<dyn Iterator<Item = XNode<'a>> + 'a> {
+        Box::new(iter.map(XNode::Tree))
+    };
+    let owner = match node {
+        XNode::Tree(node) => node,
+        XNode::Attr(owner, _, _) => {
+            return match axis {
+                Axis::Parent => tree(Box::new(iter::once(owner))),
+                Axis::Ancestor => tree(Box::new(iter::once(owner).chain(owner.ancestors()))),
+                Axis::AncestorOrSelf => Box::new(
+                    iter::once(node)
+                        .chain(iter::once(owner).chain(owner.ancestors()).map(XNode::Tree)),
+                ),
+                Axis::SelfNode => Box::new(iter::once(node)),
+                // descendants of the owner follow its attributes
+                Axis::Following => tree(Box::new(
+                    owner.descendants().skip(1).chain(following(owner)),
+                )),
+                Axis::Preceding => tree(Box::new(preceding(owner))),
+                _ => Box::new(iter::empty()),
+            };
+        }
+    };
+    match axis {
+        Axis::Ancestor => tree(Box::new(owner.ancestors())),
+        Axis::AncestorOrSelf => tree(Box::new(iter::once(owner).chain(owner.ancestors()))),
+        Axis::Attribute => {
+            let mut attrs: Vec<_> = match owner.value() {
+                Node::Element(element) => element.attrs().collect(),
+                _ => Vec::new(),
+            };
+            // attributes of scraper are unordered
+            attrs.sort_unstable();
+            Box::new(
+                attrs
+                    .into_iter()
+                    .map(move |(name, value)| XNode::Attr(owner, name, value)),
+            )
+        }
+        Axis::Child => tree(Box::new(owner.children())),
+        Axis::Descendant => tree(Box::new(owner.descendants().skip(1))),
+        Axis::DescendantOrSelf => tree(Box::new(owner.descendants())),
+        Axis::Following => tree(Box::new(following(owner))),
+        Axis::FollowingSibling => tree(Box::new(owner.next_siblings())),
+        Axis::Parent => tree(Box::new(owner.parent().into_iter())),
+        Axis::Preceding => tree(Box::new(preceding(owner))),
+        Axis::PrecedingSibling => tree(Box::new(owner.prev_siblings())),
+        Axis::SelfNode => Box::new(iter::once(node)),
+    }
+}
+
+/// nodes after `node` in document order, without its descendants
+fn following<'a>(node: NodeRef<'a, Node>) -> impl Iterator<Item = NodeRef<'a, Node>> {
+    iter::once(node)
+        .chain(node.ancestors())
+        .flat_map(|node| node.next_siblings())
+        .flat_map(|sibling| sibling.descendants())
+}
+
+/// nodes before `node` in reverse document order, without its ancestors
+fn preceding<'a>(node: NodeRef<'a, Node>) -> impl Iterator<Item = NodeRef<'a, Node>> {
+    iter::once(node)
+        .chain(node.ancestors())
+        .flat_map(|node| node.prev_siblings())
+        .flat_map(|sibling| sibling.descendants().collect::<Vec<_>>().into_iter().rev())
+}
+
+fn matches(test: &NodeTest, axis: Axis, node: XNode) -> bool {
+    match (test, node) {
+        (NodeTest::Node, _) => true,
+        (NodeTest::Any, XNode::Attr(..)) => axis == Axis::Attribute,
+        (NodeTest::Name(name), XNode::Attr(_, attr, _)) => {
+            axis == Axis::Attribute && attr.eq_ignore_ascii_case(name)
+        }
+        (NodeTest::Any, XNode::Tree(node)) => axis != Axis::Attribute && node.value().is_element(),
+        (NodeTest::Name(name), XNode::Tree(node)) => {
+            axis != Axis::Attribute
+                && node
+                    .value()
+                    .as_element()
+                    .is_some_and(|element| element.name().eq_ignore_ascii_case(name))
+        }
+        (NodeTest::Text, XNode::Tree(node)) => node.value().is_text(),
+        (NodeTest::Comment, XNode::Tree(node)) => node.value().is_comment(),
+        (NodeTest::ProcessingInstruction, XNode::Tree(node)) => {
+            matches!(node.value(), Node::ProcessingInstruction(_))
+        }
+        _ => false,
+    }
+}
+
+fn compare(op: CompareOp, left: &Value, right: &Value) -> bool {
+    let string = |node: &XNode| Value::String(node.string_value());
+    match (left, right) {
+        (Value::Nodes(left), Value::Nodes(right)) => left.iter().any(|left| {
+            let left = string(left);
+            right
+                .iter()
+                .any(|right| compare_atoms(op, &left, &string(right)))
+        }),
+        (Value::Nodes(_), Value::Bool(_)) | (Value::Bool(_), Value::Nodes(_)) => compare_atoms(
+            op,
+            &Value::Bool(left.boolean()),
+            &Value::Bool(right.boolean()),
+        ),
+        (Value::Nodes(left), right) => left
+            .iter()
+            .any(|left| compare_atoms(op, &string(left), right)),
+        (left, Value::Nodes(right)) => right
+            .iter()
+            .any(|right| compare_atoms(op, left, &string(right))),
+        (left, right) => compare_atoms(op, left, right),
+    }
+}
+
+fn compare_atoms(op: CompareOp, left: &Value, right: &Value) -> bool {
+    match op {
+        CompareOp::Eq | CompareOp::NotEq => {
+            let equal = match (left, right) {
+                (Value::Bool(_), _) | (_, Value::Bool(_)) => left.boolean() == right.boolean(),
+                (Value::Number(_), _) | (_, Value::Number(_)) => left.number() == right.number(),
+                _ => left.string() == right.string(),
+            };
+            equal == (op == CompareOp::Eq)
+        }
+        CompareOp::Lt => left.number() < right.number(),
+        CompareOp::Le => left.number() <= right.number(),
+        CompareOp::Gt => left.number() > right.number(),
+        CompareOp::Ge => left.number() >= right.number(),
+    }
+}
@@ -0,0 +1,219 @@
+use crate::XPathError;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Slash,
+    DoubleSlash,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Dot,
+    DotDot,
+    At,
+    Comma,
+    DoubleColon,
+    Pipe,
+    Plus,
+    Minus,
+    Eq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+    Div,
+    Mod,
+    Multiply,
+    /// `*` as a name test
+    Star,
+    Name(String),
+    FunctionName(String),
+    NodeType(String),
+    AxisName(String),
+    Literal(String),
+    Number(f64),
+}
+
+impl Token {
+    /// a token after which `*` is a name test and a name is not an operator
+    fn starts_operand(&self) -> bool {
+        matches!(
+            self,
+            Token::At
+                | Token::DoubleColon
+                | Token::LParen
+                | Token::LBracket
+                | Token::Comma
+                | Token::And
+                | Token::Or
+                | Token::Div
+                | Token::Mod
+                | Token::Multiply
+                | Token::Slash
+                | Token::DoubleSlash
+                | Token::Pipe
+                | Token::Plus
+                | Token::Minus
+                | Token::Eq
+                | Token::NotEq
+                | Token::Lt
+                | Token::Le
+                | Token::Gt
+                | Token::Ge
+        )
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let text = match self {
+            Token::Slash => "/",
+            Token::DoubleSlash => "//",
+            Token::LParen => "(",
+            Token::RParen => ")",
+            Token::LBracket => "[",
+            Token::RBracket => "]",
+            Token::Dot => ".",
+            Token::DotDot => "..",
+            Token::At => "@",
+            Token::Comma => ",",
+            Token::DoubleColon => "::",
+            Token::Pipe => "|",
+            Token::Plus => "+",
+            Token::Minus => "-",
+            Token::Eq => "=",
+            Token::NotEq => "!=",
+            Token::Lt => "<",
+            Token::Le => "<=",
+            Token::Gt => ">",
+            Token::Ge => ">=",
+            Token::And => "and",
+            Token::Or => "or",
+            Token::Div => "div",
+            Token::Mod => "mod",
+            Token::Multiply | Token::Star => "*",
+            Token::Name(name)
+            | Token::FunctionName(name)
+            | Token::NodeType(name)
+            | Token::AxisName(name) => name,
+            Token::Literal(literal) => return write!(f, "{:?}", literal),
+            Token::Number(number) => return write!(f, "{}", number),
+        };
+        f.write_str(text)
+    }
+}
+
+const NODE_TYPES: [&str; 4] = ["node", "text", "comment", "processing-instruction"];
+
+/// tokens with their byte offset in `xpath`
+pub fn tokenize(xpath: &str) -> Result<Vec<(usize, Token)>, XPathError> {
+    let mut tokens: Vec<(usize, Token)> = Vec::new();
+    let mut chars = xpath.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let operator = tokens
+            .last()
+            .is_some_and(|(_, token)| !token.starts_operand());
+        let rest = &xpath[start..];
+        let (token, len) = match c {
+            '/' if rest.starts_with("//") => (Token::DoubleSlash, 2),
+            '/' => (Token::Slash, 1),
+            '(' => (Token::LParen, 1),
+            ')' => (Token::RParen, 1),
+            '[' => (Token::LBracket, 1),
+            ']' => (Token::RBracket, 1),
+            '.' if rest.starts_with("..") => (Token::DotDot, 2),
+            '.' if rest[1..].starts_with(|c: char| c.is_ascii_digit()) => number(rest),
+            '.' => (Token::Dot, 1),
+            '@' => (Token::At, 1),
+            ',' => (Token::Comma, 1),
+            ':' if rest.starts_with("::") => (Token::DoubleColon, 2),
+            '|' => (Token::Pipe, 1),
+            '+' => (Token::Plus, 1),
+            '-' => (Token::Minus, 1),
+            '=' => (Token::Eq, 1),
+            '!' if rest.starts_with("!=") => (Token::NotEq, 2),
+            '<' if rest.starts_with("<=") => (Token::Le, 2),
+            '<' => (Token::Lt, 1),
+            '>' if rest.starts_with(">=") => (Token::Ge, 2),
+            '>' => (Token::Gt, 1),
+            '*' if operator => (Token::Multiply, 1),
+            '*' => (Token::Star, 1),
+            '"' | '\'' => match rest[1..].find(c) {
+                Some(end) => (Token::Literal(rest[1..=end].to_owned()), end + 2),
+                None => return Err(XPathError::new(start, "unterminated string literal")),
+            },
+            '0'..='9' => number(rest),
+            '$' => return Err(XPathError::new(start, "variables are not supported")),
+            c if is_name_start(c) => {
+                let name = &rest[..rest.find(|c| !is_name_char(c)).unwrap_or(rest.len())];
+                let after = rest[name.len()..].trim_start();
+                let token = if operator {
+                    match name {
+                        "and" => Token::And,
+                        "or" => Token::Or,
+                        "div" => Token::Div,
+                        "mod" => Token::Mod,
+                        _ => {
+                            return Err(XPathError::new(
+                                start,
+                                format!("expected an operator, found `{}`", name),
+                            ))
+                        }
+                    }
+                } else if after.starts_with("::") {
+                    Token::AxisName(name.to_owned())
+                } else if after.starts_with(':') {
+                    return Err(XPathError::new(start, "namespaces are not supported"));
+                } else if after.starts_with('(') && NODE_TYPES.contains(&name) {
+                    Token::NodeType(name.to_owned())
+                } else if after.starts_with('(') {
+                    Token::FunctionName(name.to_owned())
+                } else {
+                    Token::Name(name.to_owned())
+                };
+                (token, name.len())
+            }
+            c => {
+                return Err(XPathError::new(
+                    start,
+                    format!("unexpected character `{}`", c),
+                ))
+            }
+        };
+        tokens.push((start, token));
+        while chars.peek().is_some_and(|&(index, _)| index < start + len) {
+            chars.next();
+        }
+    }
+    Ok(tokens)
+}
+
+fn number(text: &str) -> (Token, usize) {
+    let mut len = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    if text[len..].starts_with('.') {
+        len += 1;
+        len += text[len..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len() - len);
+    }
+    // digits with an optional dot always parse
+    (Token::Number(text[..len].parse().unwrap_or(f64::NAN)), len)
+}
+
+fn is_name_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_name_char(c: char) -> bool {
+    matches!(c, '_' | '-' | '.') || c.is_alphanumeric()
+}
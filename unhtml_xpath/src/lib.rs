@@ -0,0 +1,109 @@
+//! XPath 1.0 evaluated on the trees of [scraper](https://docs.rs/scraper),
+//! used by the `xpath` attribute of unhtml.
+//!
+//! ```rust
+//! use scraper::Html;
+//! use unhtml_xpath::XPath;
+//!
+//! let html = Html::parse_document("<dl><dt>Color</dt><dd>Yellow</dd><dt>Price</dt><dd>12</dd></dl>");
+//! let xpath = XPath::parse("//dt[text()='Price']/following-sibling::dd[1]").unwrap();
+//! let selected = xpath.select(html.root_element());
+//! assert_eq!(vec!["12"], selected.iter().flat_map(|dd| dd.text()).collect::<Vec<_>>());
+//! ```
+//!
+//! Element and attribute names are matched case-insensitively like html.
+//! Variables and namespaces are not supported.
+
+mod eval;
+mod lexer;
+mod parser;
+#[cfg(test)]
+mod test;
+
+use scraper::ElementRef;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// a parsed xpath selecting elements
+#[derive(Debug, Clone, PartialEq)]
+pub struct XPath {
+    source: String,
+    expr: parser::Expr,
+}
+
+/// syntax error of an xpath
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct XPathError {
+    position: usize,
+    message: String,
+}
+
+impl XPath {
+    /// parse an expression returning a node-set
+    pub fn parse(xpath: &str) -> Result<Self, XPathError> {
+        let tokens = lexer::tokenize(xpath)?;
+        let expr = parser::Parser::new(&tokens, xpath.len()).parse()?;
+        if !expr.is_node_set() {
+            return Err(XPathError::new(0, "expected an expression selecting nodes"));
+        }
+        Ok(Self {
+            source: xpath.to_owned(),
+            expr,
+        })
+    }
+
+    /// the xpath as written
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// elements selected from the context `element` in document order,
+    /// other kinds of selected nodes like text and attributes are skipped
+    pub fn select<'a>(&self, element: ElementRef<'a>) -> Vec<ElementRef<'a>> {
+        eval::Evaluator::new(*element)
+            .select(&self.expr, *element)
+            .into_iter()
+            .filter_map(ElementRef::wrap)
+            .collect()
+    }
+}
+
+impl FromStr for XPath {
+    type Err = XPathError;
+    fn from_str(xpath: &str) -> Result<Self, XPathError> {
+        Self::parse(xpath)
+    }
+}
+
+impl Display for XPath {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl XPathError {
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+
+    /// byte offset of the error in the xpath
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for XPathError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+impl Error for XPathError {}
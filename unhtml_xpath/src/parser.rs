@@ -0,0 +1,567 @@
+use crate::lexer::Token;
+use crate::XPathError;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Axis {
+    Ancestor,
+    AncestorOrSelf,
+    Attribute,
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Following,
+    FollowingSibling,
+    Parent,
+    Preceding,
+    PrecedingSibling,
+    SelfNode,
+}
+
+impl Axis {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "ancestor" => Axis::Ancestor,
+            "ancestor-or-self" => Axis::AncestorOrSelf,
+            "attribute" => Axis::Attribute,
+            "child" => Axis::Child,
+            "descendant" => Axis::Descendant,
+            "descendant-or-self" => Axis::DescendantOrSelf,
+            "following" => Axis::Following,
+            "following-sibling" => Axis::FollowingSibling,
+            "parent" => Axis::Parent,
+            "preceding" => Axis::Preceding,
+            "preceding-sibling" => Axis::PrecedingSibling,
+            "self" => Axis::SelfNode,
+            _ => return None,
+        })
+    }
+
+    /// nodes of reverse axes are in reverse document order
+    pub fn is_reverse(self) -> bool {
+        matches!(
+            self,
+            Axis::Ancestor
+                | Axis::AncestorOrSelf
+                | Axis::Parent
+                | Axis::Preceding
+                | Axis::PrecedingSibling
+        )
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum NodeTest {
+    /// `*`, any node of the principal type of the axis
+    Any,
+    Name(String),
+    Node,
+    Text,
+    Comment,
+    ProcessingInstruction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub axis: Axis,
+    pub test: NodeTest,
+    pub predicates: Vec<Expr>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CompareOp {
+    Eq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Function {
+    Last,
+    Position,
+    Count,
+    LocalName,
+    Name,
+    String,
+    Concat,
+    StartsWith,
+    EndsWith,
+    Contains,
+    SubstringBefore,
+    SubstringAfter,
+    Substring,
+    StringLength,
+    NormalizeSpace,
+    Translate,
+    Not,
+    True,
+    False,
+    Boolean,
+    Number,
+    Sum,
+    Floor,
+    Ceiling,
+    Round,
+}
+
+impl Function {
+    /// the function and its min and max count of arguments
+    fn from_name(name: &str) -> Option<(Self, usize, usize)> {
+        Some(match name {
+            "last" => (Function::Last, 0, 0),
+            "position" => (Function::Position, 0, 0),
+            "count" => (Function::Count, 1, 1),
+            "local-name" => (Function::LocalName, 0, 1),
+            "name" => (Function::Name, 0, 1),
+            "string" => (Function::String, 0, 1),
+            "concat" => (Function::Concat, 2, usize::MAX),
+            "starts-with" => (Function::StartsWith, 2, 2),
+            "ends-with" => (Function::EndsWith, 2, 2),
+            "contains" => (Function::Contains, 2, 2),
+            "substring-before" => (Function::SubstringBefore, 2, 2),
+            "substring-after" => (Function::SubstringAfter, 2, 2),
+            "substring" => (Function::Substring, 2, 3),
+            "string-length" => (Function::StringLength, 0, 1),
+            "normalize-space" => (Function::NormalizeSpace, 0, 1),
+            "translate" => (Function::Translate, 3, 3),
+            "not" => (Function::Not, 1, 1),
+            "true" => (Function::True, 0, 0),
+            "false" => (Function::False, 0, 0),
+            "boolean" => (Function::Boolean, 1, 1),
+            "number" => (Function::Number, 0, 1),
+            "sum" => (Function::Sum, 1, 1),
+            "floor" => (Function::Floor, 1, 1),
+            "ceiling" => (Function::Ceiling, 1, 1),
+            "round" => (Function::Round, 1, 1),
+            _ => return None,
+        })
+    }
+
+    /// functions taking node-sets
+    fn takes_node_set(self) -> bool {
+        matches!(
+            self,
+            Function::Count | Function::LocalName | Function::Name | Function::Sum
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    Arith(ArithOp, Box<Expr>, Box<Expr>),
+    Negate(Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    /// a location path, from the root if `absolute`
+    Path {
+        absolute: bool,
+        steps: Vec<Step>,
+    },
+    /// a node-set filtered by predicates and followed by steps
+    Filter {
+        primary: Box<Expr>,
+        predicates: Vec<Expr>,
+        steps: Vec<Step>,
+    },
+    Literal(String),
+    Number(f64),
+    Call(Function, Vec<Expr>),
+}
+
+impl Expr {
+    pub fn is_node_set(&self) -> bool {
+        matches!(
+            self,
+            Expr::Union(..) | Expr::Path { .. } | Expr::Filter { .. }
+        )
+    }
+}
+
+pub struct Parser<'t> {
+    tokens: &'t [(usize, Token)],
+    index: usize,
+    end: usize,
+}
+
+impl<'t> Parser<'t> {
+    pub fn new(tokens: &'t [(usize, Token)], end: usize) -> Self {
+        Self {
+            tokens,
+            index: 0,
+            end,
+        }
+    }
+
+    /// parse the whole xpath as an expression
+    pub fn parse(mut self) -> Result<Expr, XPathError> {
+        let expr = self.expr()?;
+        match self.peek() {
+            None => Ok(expr),
+            Some(token) => Err(self.error(format!("unexpected `{}`", token))),
+        }
+    }
+
+    fn peek(&self) -> Option<&'t Token> {
+        self.tokens.get(self.index).map(|(_, token)| token)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map_or(self.end, |(pos, _)| *pos)
+    }
+
+    fn error(&self, message: impl Into<String>) -> XPathError {
+        XPathError::new(self.position(), message)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), XPathError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{}`", token)))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.and_expr()?;
+        while self.eat(&Token::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and_expr()?));
+        }
+        Ok(expr)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.equality_expr()?;
+        while self.eat(&Token::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.equality_expr()?));
+        }
+        Ok(expr)
+    }
+
+    fn equality_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.relational_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Eq) => CompareOp::Eq,
+                Some(Token::NotEq) => CompareOp::NotEq,
+                _ => return Ok(expr),
+            };
+            self.index += 1;
+            expr = Expr::Compare(op, Box::new(expr), Box::new(self.relational_expr()?));
+        }
+    }
+
+    fn relational_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.additive_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Lt) => CompareOp::Lt,
+                Some(Token::Le) => CompareOp::Le,
+                Some(Token::Gt) => CompareOp::Gt,
+                Some(Token::Ge) => CompareOp::Ge,
+                _ => return Ok(expr),
+            };
+            self.index += 1;
+            expr = Expr::Compare(op, Box::new(expr), Box::new(self.additive_expr()?));
+        }
+    }
+
+    fn additive_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.multiplicative_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithOp::Add,
+                Some(Token::Minus) => ArithOp::Sub,
+                _ => return Ok(expr),
+            };
+            self.index += 1;
+            expr = Expr::Arith(op, Box::new(expr), Box::new(self.multiplicative_expr()?));
+        }
+    }
+
+    fn multiplicative_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.unary_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Multiply) => ArithOp::Mul,
+                Some(Token::Div) => ArithOp::Div,
+                Some(Token::Mod) => ArithOp::Mod,
+                _ => return Ok(expr),
+            };
+            self.index += 1;
+            expr = Expr::Arith(op, Box::new(expr), Box::new(self.unary_expr()?));
+        }
+    }
+
+    fn unary_expr(&mut self) -> Result<Expr, XPathError> {
+        if self.eat(&Token::Minus) {
+            return Ok(Expr::Negate(Box::new(self.unary_expr()?)));
+        }
+        self.union_expr()
+    }
+
+    fn union_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut expr = self.path_expr()?;
+        while self.peek() == Some(&Token::Pipe) {
+            if !expr.is_node_set() {
+                return Err(self.error("expected a node-set before `|`"));
+            }
+            self.index += 1;
+            let right = self.path_expr()?;
+            if !right.is_node_set() {
+                return Err(self.error("expected a node-set after `|`"));
+            }
+            expr = Expr::Union(Box::new(expr), Box::new(right));
+        }
+        Ok(expr)
+    }
+
+    fn path_expr(&mut self) -> Result<Expr, XPathError> {
+        match self.peek() {
+            Some(Token::Slash) => {
+                self.index += 1;
+                let steps = if self.starts_step() {
+                    self.relative_path()?
+                } else {
+                    Vec::new()
+                };
+                Ok(Expr::Path {
+                    absolute: true,
+                    steps,
+                })
+            }
+            Some(Token::DoubleSlash) => {
+                self.index += 1;
+                let mut steps = vec![descendant_or_self()];
+                steps.extend(self.relative_path()?);
+                Ok(Expr::Path {
+                    absolute: true,
+                    steps,
+                })
+            }
+            Some(Token::LParen)
+            | Some(Token::Literal(_))
+            | Some(Token::Number(_))
+            | Some(Token::FunctionName(_)) => self.filter_expr(),
+            _ if self.starts_step() => Ok(Expr::Path {
+                absolute: false,
+                steps: self.relative_path()?,
+            }),
+            Some(token) => Err(self.error(format!("unexpected `{}`", token))),
+            None => Err(self.error("unexpected end of xpath")),
+        }
+    }
+
+    fn filter_expr(&mut self) -> Result<Expr, XPathError> {
+        let primary = self.primary_expr()?;
+        let predicates = self.predicates()?;
+        let steps = match self.peek() {
+            Some(Token::Slash) => {
+                self.index += 1;
+                self.relative_path()?
+            }
+            Some(Token::DoubleSlash) => {
+                self.index += 1;
+                let mut steps = vec![descendant_or_self()];
+                steps.extend(self.relative_path()?);
+                steps
+            }
+            _ => Vec::new(),
+        };
+        if predicates.is_empty() && steps.is_empty() {
+            return Ok(primary);
+        }
+        if !primary.is_node_set() {
+            return Err(self.error("predicates and steps can only follow a node-set"));
+        }
+        Ok(Expr::Filter {
+            primary: Box::new(primary),
+            predicates,
+            steps,
+        })
+    }
+
+    fn primary_expr(&mut self) -> Result<Expr, XPathError> {
+        let position = self.position();
+        match self.peek() {
+            Some(Token::LParen) => {
+                self.index += 1;
+                let expr = self.expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Literal(literal)) => {
+                self.index += 1;
+                Ok(Expr::Literal(literal.clone()))
+            }
+            Some(Token::Number(number)) => {
+                self.index += 1;
+                Ok(Expr::Number(*number))
+            }
+            Some(Token::FunctionName(name)) => {
+                self.index += 1;
+                let (function, min, max) = Function::from_name(name).ok_or_else(|| {
+                    XPathError::new(position, format!("unknown function `{}`", name))
+                })?;
+                self.expect(&Token::LParen)?;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        let arg = self.expr()?;
+                        if function.takes_node_set() && !arg.is_node_set() {
+                            return Err(XPathError::new(
+                                position,
+                                format!("`{}` expects a node-set", name),
+                            ));
+                        }
+                        args.push(arg);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(&Token::Comma)?;
+                    }
+                }
+                if args.len() < min || args.len() > max {
+                    return Err(XPathError::new(
+                        position,
+                        format!("wrong number of arguments to `{}`", name),
+                    ));
+                }
+                Ok(Expr::Call(function, args))
+            }
+            _ => Err(self.error("expected an expression")),
+        }
+    }
+
+    fn starts_step(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(Token::Dot)
+                | Some(Token::DotDot)
+                | Some(Token::At)
+                | Some(Token::AxisName(_))
+                | Some(Token::Star)
+                | Some(Token::Name(_))
+                | Some(Token::NodeType(_))
+        )
+    }
+
+    fn relative_path(&mut self) -> Result<Vec<Step>, XPathError> {
+        let mut steps = vec![self.step()?];
+        loop {
+            match self.peek() {
+                Some(Token::Slash) => {
+                    self.index += 1;
+                }
+                Some(Token::DoubleSlash) => {
+                    self.index += 1;
+                    steps.push(descendant_or_self());
+                }
+                _ => return Ok(steps),
+            }
+            steps.push(self.step()?);
+        }
+    }
+
+    fn step(&mut self) -> Result<Step, XPathError> {
+        if self.eat(&Token::Dot) {
+            return Ok(Step {
+                axis: Axis::SelfNode,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+        if self.eat(&Token::DotDot) {
+            return Ok(Step {
+                axis: Axis::Parent,
+                test: NodeTest::Node,
+                predicates: Vec::new(),
+            });
+        }
+        let axis = match self.peek() {
+            Some(Token::At) => {
+                self.index += 1;
+                Axis::Attribute
+            }
+            Some(Token::AxisName(name)) => {
+                let axis = Axis::from_name(name)
+                    .ok_or_else(|| self.error(format!("unknown axis `{}`", name)))?;
+                self.index += 1;
+                self.expect(&Token::DoubleColon)?;
+                axis
+            }
+            _ => Axis::Child,
+        };
+        let test = match self.peek() {
+            Some(Token::Star) => NodeTest::Any,
+            Some(Token::Name(name)) => NodeTest::Name(name.clone()),
+            Some(Token::NodeType(name)) => {
+                self.index += 1;
+                self.expect(&Token::LParen)?;
+                // the optional literal of `processing-instruction` is ignored
+                if name == "processing-instruction" {
+                    if let Some(Token::Literal(_)) = self.peek() {
+                        self.index += 1;
+                    }
+                }
+                if self.peek() != Some(&Token::RParen) {
+                    return Err(self.error("expected `)`"));
+                }
+                match name.as_str() {
+                    "node" => NodeTest::Node,
+                    "text" => NodeTest::Text,
+                    "comment" => NodeTest::Comment,
+                    _ => NodeTest::ProcessingInstruction,
+                }
+            }
+            _ => return Err(self.error("expected a node test")),
+        };
+        self.index += 1;
+        Ok(Step {
+            axis,
+            test,
+            predicates: self.predicates()?,
+        })
+    }
+
+    fn predicates(&mut self) -> Result<Vec<Expr>, XPathError> {
+        let mut predicates = Vec::new();
+        while self.eat(&Token::LBracket) {
+            predicates.push(self.expr()?);
+            self.expect(&Token::RBracket)?;
+        }
+        Ok(predicates)
+    }
+}
+
+/// the step of `//`
+fn descendant_or_self() -> Step {
+    Step {
+        axis: Axis::DescendantOrSelf,
+        test: NodeTest::Node,
+        predicates: Vec::new(),
+    }
+}
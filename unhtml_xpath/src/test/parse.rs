@@ -0,0 +1,45 @@
+use crate::XPath;
+
+#[test]
+fn test_parse() {
+    for xpath in &[
+        "/",
+        "//div",
+        "div/p",
+        ".//a[@href]",
+        "//dt[text()='Price']/following-sibling::dd[1]",
+        "//li[position() mod 2 = 0 and not(@hidden)]",
+        "(//td)[last()]",
+        "//*[contains(concat(' ', normalize-space(@class), ' '), ' item ')]",
+        "ancestor-or-self::*[@lang][1]",
+        "//a | //area",
+        "//div[count(p) > 1]/*",
+        "//p[. = 'div']",
+        "//div/div",
+        "//*[name() = 'div' or local-name()='span']",
+    ] {
+        assert!(XPath::parse(xpath).is_ok(), "{}", xpath);
+    }
+}
+
+#[test]
+fn test_parse_error() {
+    let error = |xpath: &str| XPath::parse(xpath).unwrap_err().to_string();
+    assert_eq!(
+        "expected an expression selecting nodes at 0",
+        error("count(//a)")
+    );
+    assert_eq!("unknown function `foo` at 4", error("//a[foo()]"));
+    assert_eq!("unknown axis `sideways` at 0", error("sideways::a"));
+    assert_eq!("expected `]` at 8", error("//a[@id "));
+    assert_eq!("unterminated string literal at 8", error("//a[@id='x]"));
+    assert_eq!("variables are not supported at 8", error("//a[@id=$id]"));
+    assert_eq!(
+        "wrong number of arguments to `contains` at 4",
+        error("//a[contains(.)]")
+    );
+    assert_eq!("`count` expects a node-set at 4", error("//a[count(1)]"));
+    assert_eq!("namespaces are not supported at 2", error("//svg:rect"));
+    assert_eq!("expected a node test at 2", error("//"));
+    assert_eq!("unexpected `]` at 3", error("//a]"));
+}
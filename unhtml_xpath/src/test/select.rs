@@ -0,0 +1,138 @@
+use crate::XPath;
+use scraper::{ElementRef, Html};
+
+const HTML: &str = r#"
+<html lang="en">
+<body>
+    <dl>
+        <dt>Color</dt><dd>Yellow</dd>
+        <dt>Price</dt><dd class="price">12.50</dd><dd>EUR</dd>
+        <dt>Size</dt><dd>3</dd>
+    </dl>
+    <ul>
+        <li id="a">a</li>
+        <li id="b" class="item odd"> b </li>
+        <li id="c" hidden>c</li>
+        <li id="d" class="item"><span>d</span></li>
+    </ul>
+    <!-- comment -->
+</body>
+</html>
+"#;
+
+/// ids or text of elements selected from the document root
+fn select(xpath: &str) -> Vec<String> {
+    let html = Html::parse_document(HTML);
+    let xpath = XPath::parse(xpath).unwrap();
+    xpath
+        .select(html.root_element())
+        .into_iter()
+        .map(describe)
+        .collect()
+}
+
+fn describe(element: ElementRef) -> String {
+    match element.value().id() {
+        Some(id) => id.to_owned(),
+        None => element.text().collect::<String>().trim().to_owned(),
+    }
+}
+
+#[test]
+fn test_select_paths() {
+    assert_eq!(vec!["a", "b", "c", "d"], select("//li"));
+    assert_eq!(vec!["a", "b", "c", "d"], select("/html/body/ul/li"));
+    assert_eq!(vec!["a", "b", "c", "d"], select("body/ul/li"));
+    assert_eq!(vec!["d"], select("//li/span/.."));
+    assert_eq!(vec!["d"], select("//LI[SPAN]"));
+    assert_eq!(vec!["c"], select("//li[@hidden]"));
+    assert_eq!(vec!["b", "d"], select("//li[@class]"));
+    assert_eq!(vec!["a", "b", "c", "d"], select("//ul//li | //li"));
+    assert_eq!(Vec::<String>::new(), select("//li/text()"));
+}
+
+#[test]
+fn test_select_axes() {
+    assert_eq!(
+        vec!["12.50"],
+        select("//dt[text()='Price']/following-sibling::dd[1]")
+    );
+    assert_eq!(
+        vec!["Price"],
+        select("//dd[@class='price']/preceding-sibling::dt[1]")
+    );
+    assert_eq!(
+        vec!["Color"],
+        select("//dd[@class='price']/preceding-sibling::dt[last()]")
+    );
+    assert_eq!(vec!["a"], select("//li[@id='b']/preceding::li"));
+    assert_eq!(vec!["c", "d"], select("//li[@id='b']/following::li"));
+    assert_eq!(
+        vec!["b", "c", "d"],
+        select("//li[@id='a']/following-sibling::*")
+    );
+    assert_eq!(
+        vec!["d"],
+        select("//span/ancestor::*[self::li or self::ol]")
+    );
+    assert_eq!(vec!["d"], select("//span/ancestor-or-self::*[2]"));
+    assert_eq!(1, select("//span/ancestor::*[@lang]").len());
+}
+
+#[test]
+fn test_select_predicates() {
+    assert_eq!(vec!["b", "d"], select("//li[position() mod 2 = 0]"));
+    assert_eq!(vec!["d"], select("(//li)[last()]"));
+    assert_eq!(vec!["c"], select("(//li)[3]"));
+    assert_eq!(vec!["a", "b"], select("//li[position() < 3]"));
+    assert_eq!(vec!["a", "b", "d"], select("//li[not(@hidden)]"));
+    assert_eq!(vec!["b"], select("//li[normalize-space() = 'b']"));
+    assert_eq!(
+        vec!["b", "d"],
+        select("//li[contains(concat(' ', normalize-space(@class), ' '), ' item ')]")
+    );
+    assert_eq!(
+        vec!["b"],
+        select("//li[starts-with(@class, 'item') and ends-with(@class, 'odd')]")
+    );
+    assert_eq!(vec!["12.50"], select("//dd[. > 10]"));
+    assert_eq!(vec!["12.50", "3"], select("//dd[number(.) = number(.)]"));
+    assert_eq!(
+        vec!["12.50"],
+        select("//dd[substring-before(., '.') = '12']")
+    );
+    assert_eq!(vec!["12.50"], select("//dd[substring(., 4) = '50']"));
+    assert_eq!(
+        vec!["EUR"],
+        select("//dd[translate(., 'EUR', 'eur') = 'eur']")
+    );
+    assert_eq!(
+        vec!["EUR"],
+        select("//dd[string-length() = 3 and floor(.) != .]")
+    );
+    assert_eq!(
+        1,
+        select("//dl[count(dd) = 4 and sum(dd[@class]) = 12.5]").len()
+    );
+    assert_eq!(vec!["d"], select("//*[name() = 'span']/parent::*"));
+    assert_eq!(vec!["a"], select("//li[@id = //li[1]/@id]"));
+    assert_eq!(
+        vec!["a", "b", "c", "d"],
+        select("//li[round(2.5) = 3 and 1 = true()]")
+    );
+}
+
+#[test]
+fn test_select_relative() {
+    let html = Html::parse_document(HTML);
+    let ul = XPath::parse("//ul").unwrap().select(html.root_element())[0];
+    let li: Vec<_> = XPath::parse("li[@class]").unwrap().select(ul);
+    assert_eq!(
+        vec!["b", "d"],
+        li.into_iter().map(describe).collect::<Vec<_>>()
+    );
+    let all: Vec<_> = XPath::parse("//dd").unwrap().select(ul);
+    assert_eq!(4, all.len());
+    let own: Vec<_> = XPath::parse(".//dd").unwrap().select(ul);
+    assert!(own.is_empty());
+}